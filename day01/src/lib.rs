@@ -1,4 +1,5 @@
 use aoc_traits::AdventOfCodeDay;
+use parsing::{Example, ParseError, Source, TryParse};
 
 #[derive(Default)]
 pub struct Solver;
@@ -8,15 +9,7 @@ impl AdventOfCodeDay for Solver {
     type Part2Output = u32;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        let len = input.lines().count();
-        let mut left = Vec::with_capacity(len);
-        let mut right = Vec::with_capacity(len);
-        for line in input.lines() {
-            let (l, r) = line.split_once("   ").expect("<num>   <num>");
-            left.push(l.parse().expect("valid number"));
-            right.push(r.parse().expect("valid number"));
-        }
-        (left, right)
+        Self::try_parse_input(input).unwrap_or_else(|e| panic!("{e}"))
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
@@ -41,18 +34,38 @@ impl AdventOfCodeDay for Solver {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::Solver;
-    use aoc_traits::AdventOfCodeDay;
+impl TryParse for Solver {
+    fn try_parse_input(input: &str) -> Result<Self::ParsedInput<'_>, ParseError> {
+        let src = Source::new(input);
+        let len = src.lines().count();
+        let mut left = Vec::with_capacity(len);
+        let mut right = Vec::with_capacity(len);
+        for line in src.lines() {
+            let (l, r) = src.split_once(line, "   ")?;
+            left.push(src.number(l)?);
+            right.push(src.number(r)?);
+        }
+        Ok((left, right))
+    }
+}
 
-    const INPUT: &str = "3   4
+impl Example for Solver {
+    const EXAMPLE: &'static str = "3   4
 4   3
 2   5
 1   3
 3   9
 3   3
 ";
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Solver;
+    use aoc_traits::AdventOfCodeDay;
+    use parsing::Example;
+
+    const INPUT: &str = Solver::EXAMPLE;
 
     #[test]
     fn test_part1() {
@@ -65,4 +78,51 @@ mod tests {
         let parsed = Solver::parse_input(INPUT);
         assert_eq!(Solver::solve_part2(&parsed), 31);
     }
+
+    #[test]
+    fn test_input_variants() {
+        for input in parsing::variants(INPUT) {
+            let parsed = Solver::parse_input(&input);
+            assert_eq!(Solver::solve_part1(&parsed), 11);
+        }
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use crate::Solver;
+    use aoc_traits::AdventOfCodeDay;
+    use proptest::prelude::*;
+
+    /// Pairs up the smallest numbers left in both lists until they are empty.
+    fn reference_part1(pairs: &[(u32, u32)]) -> u32 {
+        let mut left = pairs.iter().map(|p| p.0).collect::<Vec<_>>();
+        let mut right = pairs.iter().map(|p| p.1).collect::<Vec<_>>();
+        let mut sum = 0;
+        while !left.is_empty() {
+            let l = left.iter().enumerate().min_by_key(|(_, n)| **n).unwrap().0;
+            let r = right.iter().enumerate().min_by_key(|(_, n)| **n).unwrap().0;
+            sum += left.remove(l).abs_diff(right.remove(r));
+        }
+        sum
+    }
+
+    fn reference_part2(pairs: &[(u32, u32)]) -> u32 {
+        let mut sum = 0;
+        for (l, _) in pairs {
+            sum += l * pairs.iter().filter(|(_, r)| l == r).count() as u32;
+        }
+        sum
+    }
+
+    proptest! {
+        // a narrow range, so the lists share numbers
+        #[test]
+        fn matches_reference(pairs in prop::collection::vec((10000..10050u32, 10000..10050u32), 1..100)) {
+            let input = pairs.iter().map(|(l, r)| format!("{l}   {r}\n")).collect::<String>();
+            let parsed = Solver::parse_input(&input);
+            prop_assert_eq!(Solver::solve_part1(&parsed), reference_part1(&pairs));
+            prop_assert_eq!(Solver::solve_part2(&parsed), reference_part2(&pairs));
+        }
+    }
 }
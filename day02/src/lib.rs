@@ -1,8 +1,9 @@
 use aoc_traits::AdventOfCodeDay;
 use itertools::Itertools;
+use parsing::{Example, ParseError, Source, TryParse};
 
 fn increasing(pair: (&u32, &u32)) -> bool {
-    (1..=3).contains(&(pair.0 - pair.1))
+    (1..=3).contains(&(*pair.0 as i32 - *pair.1 as i32))
 }
 
 fn decreasing(pair: (&u32, &u32)) -> bool {
@@ -17,14 +18,7 @@ impl AdventOfCodeDay for Solver {
     type Part2Output = u32;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        input
-            .lines()
-            .map(|line| {
-                line.split(' ')
-                    .map(|x| x.parse().expect("valid number"))
-                    .collect()
-            })
-            .collect()
+        Self::try_parse_input(input).unwrap_or_else(|e| panic!("{e}"))
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
@@ -76,18 +70,32 @@ impl AdventOfCodeDay for Solver {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::Solver;
-    use aoc_traits::AdventOfCodeDay;
+impl TryParse for Solver {
+    fn try_parse_input(input: &str) -> Result<Self::ParsedInput<'_>, ParseError> {
+        let src = Source::new(input);
+        src.lines()
+            .map(|line| line.split(' ').map(|x| src.number(x)).collect())
+            .collect()
+    }
+}
 
-    const INPUT: &str = "7 6 4 2 1
+impl Example for Solver {
+    const EXAMPLE: &'static str = "7 6 4 2 1
 1 2 7 8 9
 9 7 6 2 1
 1 3 2 4 5
 8 6 4 4 1
 1 3 6 7 9
 ";
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Solver;
+    use aoc_traits::AdventOfCodeDay;
+    use parsing::Example;
+
+    const INPUT: &str = Solver::EXAMPLE;
 
     #[test]
     fn test_part1() {
@@ -100,4 +108,64 @@ mod tests {
         let parsed = Solver::parse_input(INPUT);
         assert_eq!(Solver::solve_part2(&parsed), 4);
     }
+
+    #[test]
+    fn test_input_variants() {
+        for input in parsing::variants(INPUT) {
+            let parsed = Solver::parse_input(&input);
+            assert_eq!(Solver::solve_part1(&parsed), 2);
+        }
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use crate::Solver;
+    use aoc_traits::AdventOfCodeDay;
+    use proptest::prelude::*;
+
+    fn safe(report: &[u32]) -> bool {
+        let diffs = report
+            .windows(2)
+            .map(|w| w[1] as i64 - w[0] as i64)
+            .collect::<Vec<_>>();
+        diffs.iter().all(|d| (1..=3).contains(d)) || diffs.iter().all(|d| (-3..=-1).contains(d))
+    }
+
+    /// Tries the report without each of its levels.
+    fn dampened(report: &[u32]) -> bool {
+        (0..report.len()).any(|i| safe(&[&report[..i], &report[i + 1..]].concat()))
+    }
+
+    /// Levels that mostly change by a few steps, so some reports are safe.
+    fn reports() -> impl Strategy<Value = Vec<Vec<u32>>> {
+        let report =
+            (30..=70u32, prop::collection::vec(-4..=4i32, 0..8)).prop_map(|(first, diffs)| {
+                let mut level = first;
+                let mut report = vec![level];
+                for diff in diffs {
+                    level = level.wrapping_add_signed(diff);
+                    report.push(level);
+                }
+                report
+            });
+        prop::collection::vec(report, 1..50)
+    }
+
+    proptest! {
+        #[test]
+        fn matches_reference(reports in reports()) {
+            let input = reports
+                .iter()
+                .map(|report| {
+                    let levels = report.iter().map(u32::to_string).collect::<Vec<_>>();
+                    format!("{}\n", levels.join(" "))
+                })
+                .collect::<String>();
+            let parsed = Solver::parse_input(&input);
+            let count = |safe: fn(&[u32]) -> bool| reports.iter().filter(|r| safe(r)).count() as u32;
+            prop_assert_eq!(Solver::solve_part1(&parsed), count(safe));
+            prop_assert_eq!(Solver::solve_part2(&parsed), count(dampened));
+        }
+    }
 }
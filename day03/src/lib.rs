@@ -1,4 +1,5 @@
 use aoc_traits::AdventOfCodeDay;
+use parsing::{Example, ParseError, TryParse};
 use regex::Regex;
 
 #[derive(Default)]
@@ -9,7 +10,7 @@ impl AdventOfCodeDay for Solver {
     type Part2Output = u32;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        input
+        Self::try_parse_input(input).unwrap_or_else(|e| panic!("{e}"))
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
@@ -48,12 +49,24 @@ impl AdventOfCodeDay for Solver {
     }
 }
 
+impl TryParse for Solver {
+    fn try_parse_input(input: &str) -> Result<Self::ParsedInput<'_>, ParseError> {
+        Ok(input)
+    }
+}
+
+impl Example for Solver {
+    const EXAMPLE: &'static str =
+        "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+}
+
 #[cfg(test)]
 mod tests {
     use crate::Solver;
     use aoc_traits::AdventOfCodeDay;
+    use parsing::Example;
 
-    const INPUT: &str = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+    const INPUT: &str = Solver::EXAMPLE;
 
     #[test]
     fn test_part1() {
@@ -66,4 +79,78 @@ mod tests {
         let parsed = Solver::parse_input(INPUT);
         assert_eq!(Solver::solve_part2(&parsed), 48);
     }
+
+    #[test]
+    fn test_input_variants() {
+        for input in parsing::variants(INPUT) {
+            let parsed = Solver::parse_input(&input);
+            assert_eq!(Solver::solve_part1(&parsed), 161);
+        }
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use crate::Solver;
+    use aoc_traits::AdventOfCodeDay;
+    use proptest::prelude::*;
+
+    /// Up to three digits at the start of `s`, and how many there were.
+    fn number(s: &[u8]) -> Option<(u32, usize)> {
+        let len = s.iter().take(3).take_while(|c| c.is_ascii_digit()).count();
+        let n = s[..len].iter().fold(0, |n, c| 10 * n + (c - b'0') as u32);
+        (len > 0).then_some((n, len))
+    }
+
+    /// The product of the `mul` at the start of `s`, and its length.
+    fn mul(s: &[u8]) -> Option<(u32, usize)> {
+        let s = s.strip_prefix(b"mul(")?;
+        let (a, a_len) = number(s)?;
+        let s = s[a_len..].strip_prefix(b",")?;
+        let (b, b_len) = number(s)?;
+        s[b_len..].strip_prefix(b")")?;
+        Some((a * b, 4 + a_len + 1 + b_len + 1))
+    }
+
+    /// Tries every position for an instruction, skipping over the ones found. `do()` and
+    /// `don't()` only count when `conditionals` is set.
+    fn reference(memory: &str, conditionals: bool) -> u32 {
+        let memory = memory.as_bytes();
+        let (mut i, mut enabled, mut sum) = (0, true, 0);
+        while i < memory.len() {
+            if let Some((product, len)) = mul(&memory[i..]) {
+                sum += if enabled { product } else { 0 };
+                i += len;
+                continue;
+            }
+            if memory[i..].starts_with(b"do()") {
+                enabled = true;
+            } else if memory[i..].starts_with(b"don't()") {
+                enabled = !conditionals;
+            }
+            i += 1;
+        }
+        sum
+    }
+
+    /// Instructions, some with four digit numbers, between the characters they are made of.
+    fn memory() -> impl Strategy<Value = String> {
+        let piece = prop_oneof![
+            4 => (0..=1200u32, 0..=1200u32).prop_map(|(a, b)| format!("mul({a},{b})")),
+            1 => Just("do()".to_string()),
+            1 => Just("don't()".to_string()),
+            4 => prop::sample::select(&["m", "u", "l", "(", ")", ",", "7", "d", "o", "n", "'", "t", " ", "\n"][..])
+                .prop_map(str::to_string),
+        ];
+        prop::collection::vec(piece, 1..100).prop_map(|pieces| pieces.concat())
+    }
+
+    proptest! {
+        #[test]
+        fn matches_reference(memory in memory()) {
+            let parsed = Solver::parse_input(&memory);
+            prop_assert_eq!(Solver::solve_part1(&parsed), reference(&memory, false));
+            prop_assert_eq!(Solver::solve_part2(&parsed), reference(&memory, true));
+        }
+    }
 }
@@ -1,4 +1,8 @@
+use std::convert::Infallible;
+
 use aoc_traits::AdventOfCodeDay;
+use grid::{Direction8, Grid};
+use parsing::{Example, ParseError, Source, TryParse};
 
 const X: u8 = 88;
 const M: u8 = 77;
@@ -11,111 +15,21 @@ const SAM: [u8; 3] = [S, A, M];
 #[derive(Default)]
 pub struct Solver;
 impl AdventOfCodeDay for Solver {
-    type ParsedInput<'a> = (Vec<u8>, usize, usize);
+    type ParsedInput<'a> = Grid<u8>;
     type Part1Output = u32;
     type Part2Output = u32;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        let mut rows = 0;
-        let mut cols = 0;
-        let data = input
-            .lines()
-            .flat_map(|line| {
-                let row = line.as_bytes().to_vec();
-                cols = row.len();
-                rows += 1;
-                row
-            })
-            .collect();
-        (data, rows, cols)
+        Self::try_parse_input(input).unwrap_or_else(|e| panic!("{e}"))
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
-        let data = &input.0;
-        let rows = input.1;
-        let cols = input.2;
-
-        let index = |row: usize, col: usize| -> usize { col + cols * row };
-
         let mut count = 0;
-
-        for row in 0..rows {
-            for col in 0..cols {
-                if data[index(row, col)] == X {
-                    let north = row >= 3;
-                    let east = col < cols - 3;
-                    let south = row < rows - 3;
-                    let west = col >= 3;
-                    if north {
-                        let n0 = data[index(row - 1, col)];
-                        let n1 = data[index(row - 2, col)];
-                        let n2 = data[index(row - 3, col)];
-                        if [n0, n1, n2] == MAS {
-                            count += 1;
-                        }
-                    }
-
-                    if north && east {
-                        let ne0 = data[index(row - 1, col + 1)];
-                        let ne1 = data[index(row - 2, col + 2)];
-                        let ne2 = data[index(row - 3, col + 3)];
-                        if [ne0, ne1, ne2] == MAS {
-                            count += 1;
-                        }
-                    }
-
-                    if east {
-                        let e0 = data[index(row, col + 1)];
-                        let e1 = data[index(row, col + 2)];
-                        let e2 = data[index(row, col + 3)];
-                        if [e0, e1, e2] == MAS {
-                            count += 1;
-                        }
-                    }
-
-                    if south && east {
-                        let se0 = data[index(row + 1, col + 1)];
-                        let se1 = data[index(row + 2, col + 2)];
-                        let se2 = data[index(row + 3, col + 3)];
-                        if [se0, se1, se2] == MAS {
-                            count += 1;
-                        }
-                    }
-
-                    if south {
-                        let s0 = data[index(row + 1, col)];
-                        let s1 = data[index(row + 2, col)];
-                        let s2 = data[index(row + 3, col)];
-                        if [s0, s1, s2] == MAS {
-                            count += 1;
-                        }
-                    }
-
-                    if south && west {
-                        let sw0 = data[index(row + 1, col - 1)];
-                        let sw1 = data[index(row + 2, col - 2)];
-                        let sw2 = data[index(row + 3, col - 3)];
-                        if [sw0, sw1, sw2] == MAS {
-                            count += 1;
-                        }
-                    }
-
-                    if west {
-                        let w0 = data[index(row, col - 1)];
-                        let w1 = data[index(row, col - 2)];
-                        let w2 = data[index(row, col - 3)];
-                        if [w0, w1, w2] == MAS {
-                            count += 1;
-                        }
-                    }
-
-                    if north && west {
-                        let nw0 = data[index(row - 1, col - 1)];
-                        let nw1 = data[index(row - 2, col - 2)];
-                        let nw2 = data[index(row - 3, col - 3)];
-                        if [nw0, nw1, nw2] == MAS {
-                            count += 1;
-                        }
+        for (pos, cell) in input.iter() {
+            if *cell == X {
+                for dir in Direction8::ALL {
+                    if input.ray(pos, dir.step()).take(3).map(|p| input[p]).eq(MAS) {
+                        count += 1;
                     }
                 }
             }
@@ -124,30 +38,16 @@ impl AdventOfCodeDay for Solver {
     }
 
     fn solve_part2(input: &Self::ParsedInput<'_>) -> Self::Part2Output {
-        let data = &input.0;
-        let rows = input.1;
-        let cols = input.2;
-
-        let index = |row: usize, col: usize| -> usize { col + cols * row };
-
         let mut count = 0;
-
-        for row in 1..rows - 1 {
-            for col in 1..cols - 1 {
-                if data[index(row, col)] == A {
-                    let da0 = data[index(row - 1, col - 1)];
-                    let da1 = data[index(row, col)];
-                    let da2 = data[index(row + 1, col + 1)];
-                    let db0 = data[index(row - 1, col + 1)];
-                    let db1 = data[index(row, col)];
-                    let db2 = data[index(row + 1, col - 1)];
-
-                    let da = [da0, da1, da2];
-                    let db = [db0, db1, db2];
-
-                    if (da == MAS || da == SAM) && (db == MAS || db == SAM) {
-                        count += 1;
-                    }
+        for (pos, cell) in input.iter() {
+            if *cell == A {
+                let at = |step| input.offset(pos, step).map(|p| input[p]);
+                let da = [at((-1, -1)), Some(A), at((1, 1))];
+                let db = [at((-1, 1)), Some(A), at((1, -1))];
+                let mas = |d: [Option<u8>; 3]| d == MAS.map(Some) || d == SAM.map(Some);
+
+                if mas(da) && mas(db) {
+                    count += 1;
                 }
             }
         }
@@ -155,12 +55,16 @@ impl AdventOfCodeDay for Solver {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::Solver;
-    use aoc_traits::AdventOfCodeDay;
+impl TryParse for Solver {
+    fn try_parse_input(input: &str) -> Result<Self::ParsedInput<'_>, ParseError> {
+        Grid::parse(&Source::new(input), input, |_, c| {
+            Ok::<_, Infallible>(c as u8)
+        })
+    }
+}
 
-    const INPUT: &str = "MMMSXXMASM
+impl Example for Solver {
+    const EXAMPLE: &'static str = "MMMSXXMASM
 MSAMXMSMSA
 AMXSXMAAMM
 MSAMASMSMX
@@ -171,6 +75,15 @@ SAXAMASAAA
 MAMMMXMMMM
 MXMXAXMASX
 ";
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Solver;
+    use aoc_traits::AdventOfCodeDay;
+    use parsing::Example;
+
+    const INPUT: &str = Solver::EXAMPLE;
 
     #[test]
     fn test_part1() {
@@ -183,4 +96,68 @@ MXMXAXMASX
         let parsed = Solver::parse_input(INPUT);
         assert_eq!(Solver::solve_part2(&parsed), 9);
     }
+
+    #[test]
+    fn test_input_variants() {
+        for input in parsing::variants(INPUT) {
+            let parsed = Solver::parse_input(&input);
+            assert_eq!(Solver::solve_part1(&parsed), 18);
+        }
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use crate::Solver;
+    use aoc_traits::AdventOfCodeDay;
+    use proptest::prelude::*;
+
+    /// The letters from `(row, col)` on in steps of `(dr, dc)`, if they stay inside the grid.
+    fn word(grid: &[Vec<u8>], (row, col): (isize, isize), (dr, dc): (isize, isize)) -> Vec<u8> {
+        (0..4)
+            .map_while(|i| {
+                let row = grid.get(usize::try_from(row + i * dr).ok()?)?;
+                row.get(usize::try_from(col + i * dc).ok()?).copied()
+            })
+            .collect()
+    }
+
+    /// Spells out the words from every cell, in every direction for part 1 and along both
+    /// diagonals through it for part 2.
+    fn reference(grid: &[Vec<u8>]) -> (u32, u32) {
+        let (mut xmas, mut x_mas) = (0, 0);
+        for row in 0..grid.len() as isize {
+            for col in 0..grid[0].len() as isize {
+                for dr in -1..=1 {
+                    for dc in -1..=1 {
+                        xmas += (word(grid, (row, col), (dr, dc)) == b"XMAS") as u32;
+                    }
+                }
+                let mas = |dc| {
+                    let word = word(grid, (row - 1, col - dc), (1, dc));
+                    word.starts_with(b"MAS") || word.starts_with(b"SAM")
+                };
+                x_mas += (mas(1) && mas(-1)) as u32;
+            }
+        }
+        (xmas, x_mas)
+    }
+
+    fn grid() -> impl Strategy<Value = Vec<Vec<u8>>> {
+        (1..12usize, 1..12usize).prop_flat_map(|(rows, cols)| {
+            let cell = prop::sample::select(&b"XMAS"[..]);
+            prop::collection::vec(prop::collection::vec(cell, cols), rows)
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn matches_reference(grid in grid()) {
+            let input = grid.iter().map(|row| String::from_utf8_lossy(row) + "\n").collect::<String>();
+            let parsed = Solver::parse_input(&input);
+            let (part1, part2) = reference(&grid);
+            prop_assert_eq!(Solver::solve_part1(&parsed), part1);
+            prop_assert_eq!(Solver::solve_part2(&parsed), part2);
+        }
+    }
 }
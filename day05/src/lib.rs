@@ -1,6 +1,7 @@
 use aoc_traits::AdventOfCodeDay;
 use intmap::{Entry, IntMap};
 use itertools::Itertools;
+use parsing::{Example, ParseError, Source, TryParse};
 
 fn valid(line: &[u32], rules: &IntMap<Vec<u32>>) -> bool {
     line.iter().tuple_windows::<(_, _)>().all(|pair| {
@@ -18,23 +19,7 @@ impl AdventOfCodeDay for Solver {
     type Part2Output = u32;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        let (rules_, updates) = input.split_once("\n\n").unwrap();
-        let mut rules = IntMap::new();
-        for rule in rules_.lines() {
-            let (a, b) = rule.split_once('|').unwrap();
-            let a = a.parse().unwrap();
-            let b = b.parse().unwrap();
-            let follow = match rules.entry(a) {
-                Entry::Occupied(entry) => entry.into_mut(),
-                Entry::Vacant(entry) => entry.insert(vec![]),
-            };
-            follow.push(b);
-        }
-        let updates = updates
-            .lines()
-            .map(|line| line.split(',').map(|p| p.parse().unwrap()).collect())
-            .collect();
-        (rules, updates)
+        Self::try_parse_input(input).unwrap_or_else(|e| panic!("{e}"))
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
@@ -84,12 +69,30 @@ impl AdventOfCodeDay for Solver {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::Solver;
-    use aoc_traits::AdventOfCodeDay;
+impl TryParse for Solver {
+    fn try_parse_input(input: &str) -> Result<Self::ParsedInput<'_>, ParseError> {
+        let src = Source::new(input);
+        let (rules_, updates) = src.two_sections()?;
+        let mut rules = IntMap::new();
+        for rule in parsing::lines(rules_) {
+            let (a, b) = src.split_once(rule, "|")?;
+            let a = src.number(a)?;
+            let b = src.number(b)?;
+            let follow = match rules.entry(a) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(vec![]),
+            };
+            follow.push(b);
+        }
+        let updates = parsing::lines(updates)
+            .map(|line| line.split(',').map(|p| src.number(p)).collect())
+            .collect::<Result<_, _>>()?;
+        Ok((rules, updates))
+    }
+}
 
-    const INPUT: &str = "47|53
+impl Example for Solver {
+    const EXAMPLE: &'static str = "47|53
 97|13
 97|61
 97|47
@@ -118,6 +121,15 @@ mod tests {
 61,13,29
 97,13,75,29,47
 ";
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Solver;
+    use aoc_traits::AdventOfCodeDay;
+    use parsing::Example;
+
+    const INPUT: &str = Solver::EXAMPLE;
 
     #[test]
     fn test_part1() {
@@ -130,4 +142,79 @@ mod tests {
         let parsed = Solver::parse_input(INPUT);
         assert_eq!(Solver::solve_part2(&parsed), 123);
     }
+
+    #[test]
+    fn test_input_variants() {
+        for input in parsing::variants(INPUT) {
+            let parsed = Solver::parse_input(&input);
+            assert_eq!(Solver::solve_part1(&parsed), 143);
+        }
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use crate::Solver;
+    use aoc_traits::AdventOfCodeDay;
+    use proptest::prelude::*;
+
+    /// `order` lists every page before the pages that have to follow it.
+    fn render(order: &[u32], updates: &[Vec<u32>]) -> String {
+        let mut out = String::new();
+        for (i, a) in order.iter().enumerate() {
+            for b in &order[i + 1..] {
+                out.push_str(&format!("{a}|{b}\n"));
+            }
+        }
+        out.push('\n');
+        for update in updates {
+            let pages = update.iter().map(u32::to_string).collect::<Vec<_>>();
+            out.push_str(&format!("{}\n", pages.join(",")));
+        }
+        out
+    }
+
+    /// Sums the middle pages of the updates that were in order and, separately, of the others
+    /// once they are sorted.
+    fn reference(order: &[u32], updates: &[Vec<u32>]) -> (u32, u32) {
+        let (mut ordered, mut reordered) = (0, 0);
+        for update in updates {
+            let mut sorted = update.clone();
+            sorted.sort_by_key(|page| order.iter().position(|p| p == page));
+            if sorted == *update {
+                ordered += sorted[sorted.len() / 2];
+            } else {
+                reordered += sorted[sorted.len() / 2];
+            }
+        }
+        (ordered, reordered)
+    }
+
+    /// Like the puzzle, the rules order every pair of pages and updates have an odd number of
+    /// distinct pages.
+    fn manual() -> impl Strategy<Value = (Vec<u32>, Vec<Vec<u32>>)> {
+        let pages = (10..100).collect::<Vec<u32>>();
+        prop::sample::subsequence(pages, 3..20)
+            .prop_shuffle()
+            .prop_flat_map(|order| {
+                let len = order.len();
+                let update = prop::sample::subsequence(order.clone(), 1..=len)
+                    .prop_shuffle()
+                    .prop_map(|mut update| {
+                        update.truncate((update.len() - 1) | 1);
+                        update
+                    });
+                (Just(order), prop::collection::vec(update, 1..20))
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn matches_reference((order, updates) in manual()) {
+            let parsed = Solver::parse_input(&render(&order, &updates));
+            let (part1, part2) = reference(&order, &updates);
+            prop_assert_eq!(Solver::solve_part1(&parsed), part1);
+            prop_assert_eq!(Solver::solve_part2(&parsed), part2);
+        }
+    }
 }
@@ -1,188 +1,87 @@
+use std::convert::Infallible;
+
 use aoc_traits::AdventOfCodeDay;
+use grid::{Direction, Grid, Position};
+use parsing::{Example, ParseError, Source, TryParse};
+use render::{Color, Picture, Render, Simulation, Tile};
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-pub enum Direction {
-    Up = 0,
-    Down = 1,
-    Left = 2,
-    Right = 3,
-}
+/// The positions the guard walks over before leaving the map, or before walking in a loop on
+/// maps they never leave.
+fn patrol((data, start, dir): &(Grid<u8>, Position, Direction)) -> Grid<bool> {
+    let mut pos = *start;
+    let mut dir = *dir;
 
-impl Direction {
-    fn offset(&self) -> (isize, isize) {
-        match self {
-            Direction::Up => (-1, 0),
-            Direction::Down => (1, 0),
-            Direction::Left => (0, -1),
-            Direction::Right => (0, 1),
+    let mut seen = Grid::new(data.rows(), data.cols(), [false; 4]);
+    seen[pos][dir as usize] = true;
+
+    while let Some(next) = data.offset(pos, dir.step()) {
+        if data[next] == b'#' {
+            dir = dir.turn_right();
+        } else {
+            pos = next;
+        }
+        if seen[pos][dir as usize] {
+            break;
         }
+        seen[pos][dir as usize] = true;
     }
+
+    seen.map(|dirs| dirs.contains(&true))
 }
 
 #[derive(Default)]
 pub struct Solver;
 impl AdventOfCodeDay for Solver {
-    type ParsedInput<'a> = (Vec<Vec<u8>>, (usize, usize), Direction);
+    type ParsedInput<'a> = (Grid<u8>, Position, Direction);
     type Part1Output = u32;
     type Part2Output = u32;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        let mut start = None;
-        let mut dir = None;
-        (
-            input
-                .lines()
-                .enumerate()
-                .map(|(row, line)| {
-                    line.chars()
-                        .enumerate()
-                        .map(|(col, c)| {
-                            if c == '^' {
-                                start = Some((row, col));
-                                dir = Some(Direction::Up);
-                            } else if c == 'v' {
-                                start = Some((row, col));
-                                dir = Some(Direction::Down);
-                            } else if c == '<' {
-                                start = Some((row, col));
-                                dir = Some(Direction::Left);
-                            } else if c == '>' {
-                                start = Some((row, col));
-                                dir = Some(Direction::Right);
-                            }
-                            c as u8
-                        })
-                        .collect()
-                })
-                .collect(),
-            start.unwrap(),
-            dir.unwrap(),
-        )
+        Self::try_parse_input(input).unwrap_or_else(|e| panic!("{e}"))
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
-        let data = &input.0;
-        let rows = data.len();
-        let cols = data[0].len();
-        let (mut r, mut c) = input.1;
-        let mut dir = input.2;
-
-        let mut visited = vec![vec![false; cols]; rows];
-        visited[r][c] = true;
-
-        loop {
-            let offset = dir.offset();
-            let next_r = r as isize + offset.0;
-            let next_c = c as isize + offset.1;
-
-            if next_r >= rows as isize || next_r < 0 || next_c >= cols as isize || next_c < 0 {
-                break;
-            }
-
-            let peek = data[next_r as usize][next_c as usize];
-
-            if peek == b'#' {
-                dir = match dir {
-                    Direction::Up => Direction::Right,
-                    Direction::Down => Direction::Left,
-                    Direction::Left => Direction::Up,
-                    Direction::Right => Direction::Down,
-                }
-            } else {
-                r = next_r as usize;
-                c = next_c as usize;
-                visited[r][c] = true;
-            }
-        }
-
-        visited
-            .iter()
-            .flatten()
-            .map(|v| if *v { 1 } else { 0 })
-            .sum()
+        patrol(input).iter().filter(|(_, v)| **v).count() as u32
     }
 
     fn solve_part2(input: &Self::ParsedInput<'_>) -> Self::Part2Output {
-        let data = input.0.clone();
-        let rows = data.len();
-        let cols = data[0].len();
-        let (r, c) = input.1;
+        let data = &input.0;
+        let start = input.1;
         let dir = input.2;
 
-        let terminates = |data: &[Vec<u8>], mut r: usize, mut c: usize, mut dir: Direction| {
-            let mut visited = vec![vec![[false; 4]; cols]; rows];
-            visited[r][c][dir as usize] = true;
+        let terminates = |data: &Grid<u8>, mut pos: Position, mut dir: Direction| {
+            let mut visited = Grid::new(data.rows(), data.cols(), [false; 4]);
+            visited[pos][dir as usize] = true;
             loop {
-                let offset = dir.offset();
-                let next_r = r as isize + offset.0;
-                let next_c = c as isize + offset.1;
-
-                if next_r >= rows as isize || next_r < 0 || next_c >= cols as isize || next_c < 0 {
+                let Some(next) = data.offset(pos, dir.step()) else {
                     break true;
-                }
-
-                let peek = data[next_r as usize][next_c as usize];
+                };
 
-                if peek == b'#' {
-                    dir = match dir {
-                        Direction::Up => Direction::Right,
-                        Direction::Down => Direction::Left,
-                        Direction::Left => Direction::Up,
-                        Direction::Right => Direction::Down,
-                    };
+                if data[next] == b'#' {
+                    dir = dir.turn_right();
                 } else {
-                    r = next_r as usize;
-                    c = next_c as usize;
-                    if visited[r][c][dir as usize] {
-                        break false;
-                    }
-                    visited[r][c][dir as usize] = true;
-                }
-            }
-        };
-
-        let path = |data: &[Vec<u8>], mut r: usize, mut c: usize, mut dir: Direction| {
-            let mut p = vec![vec![false; cols]; rows];
-            loop {
-                p[r][c] = true;
-
-                let offset = dir.offset();
-                let next_r = r as isize + offset.0;
-                let next_c = c as isize + offset.1;
-
-                if next_r >= rows as isize || next_r < 0 || next_c >= cols as isize || next_c < 0 {
-                    return p;
+                    pos = next;
                 }
-
-                let peek = data[next_r as usize][next_c as usize];
-
-                if peek == b'#' {
-                    dir = match dir {
-                        Direction::Up => Direction::Right,
-                        Direction::Down => Direction::Left,
-                        Direction::Left => Direction::Up,
-                        Direction::Right => Direction::Down,
-                    };
-                } else {
-                    r = next_r as usize;
-                    c = next_c as usize;
+                // checked after turns too, a guard boxed in on all sides never moves
+                if visited[pos][dir as usize] {
+                    break false;
                 }
+                visited[pos][dir as usize] = true;
             }
         };
 
-        let p = path(&data, r, c, dir);
+        let p = patrol(input);
         let mut d = data.clone();
 
         let mut count = 0;
-        for row in 0..rows {
-            for col in 0..cols {
-                if p[row][col] {
-                    d[row][col] = b'#';
-                    if !terminates(&d, r, c, dir) {
-                        count += 1;
-                    }
-                    d[row][col] = b'.';
+        for (pos, on_path) in p.iter() {
+            // nothing can be put where the guard is standing
+            if *on_path && pos != start {
+                d[pos] = b'#';
+                if !terminates(&d, start, dir) {
+                    count += 1;
                 }
+                d[pos] = b'.';
             }
         }
 
@@ -190,12 +89,74 @@ impl AdventOfCodeDay for Solver {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::Solver;
-    use aoc_traits::AdventOfCodeDay;
+impl TryParse for Solver {
+    fn try_parse_input(input: &str) -> Result<Self::ParsedInput<'_>, ParseError> {
+        let mut start = None;
+        let src = Source::new(input);
+        let map = Grid::parse(&src, input, |pos, c| {
+            if let Some(dir) = Direction::from_arrow(c) {
+                start = Some((pos, dir));
+            }
+            Ok::<_, Infallible>(c as u8)
+        })?;
+        let Some((start, dir)) = start else {
+            return Err(src.error("", "no guard (`^`, `v`, `<` or `>`) on the map"));
+        };
+        Ok((map, start, dir))
+    }
+}
+
+/// The map, with the visited positions behind it.
+fn picture(data: &Grid<u8>, visited: &Grid<bool>) -> Picture {
+    let mut picture = Picture::new(data, |cell| match cell {
+        b'#' => Tile::new('#', Color::WHITE),
+        b'.' => Tile::new('.', Color::GRAY),
+        c => Tile::new(*c as char, Color::YELLOW),
+    });
+    picture.overlay(visited.positions().filter(|pos| visited[*pos]), Color::BLUE);
+    picture
+}
+
+impl Render for Solver {
+    fn render(input: &Self::ParsedInput<'_>) -> Picture {
+        picture(&input.0, &patrol(input))
+    }
+}
+
+impl Simulation for Solver {
+    /// Where the guard is, which way it faces and where it has been.
+    type State = (Position, Direction, Grid<bool>);
 
-    const INPUT: &str = "....#.....
+    /// A state per step or turn, until the guard leaves the map. Never ends if the guard walks
+    /// in a loop.
+    fn states<'a>(input: &'a Self::ParsedInput<'_>) -> impl Iterator<Item = Self::State> + 'a {
+        let (data, start, dir) = input;
+        let mut visited = Grid::new(data.rows(), data.cols(), false);
+        visited[*start] = true;
+
+        std::iter::successors(Some((*start, *dir, visited)), |(pos, dir, visited)| {
+            let next = data.offset(*pos, dir.step())?;
+            if data[next] == b'#' {
+                Some((*pos, dir.turn_right(), visited.clone()))
+            } else {
+                let mut visited = visited.clone();
+                visited[next] = true;
+                Some((next, *dir, visited))
+            }
+        })
+    }
+
+    fn draw(input: &Self::ParsedInput<'_>, (pos, dir, visited): &Self::State) -> Picture {
+        let mut picture = picture(&input.0, visited);
+        picture
+            .overlay([*pos], Color::YELLOW)
+            .draw(*pos, dir.arrow(), Color::BLACK);
+        picture
+    }
+}
+
+impl Example for Solver {
+    const EXAMPLE: &'static str = "....#.....
 .........#
 ..........
 ..#.......
@@ -206,6 +167,16 @@ mod tests {
 #.........
 ......#...
 ";
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Solver;
+    use aoc_traits::AdventOfCodeDay;
+    use parsing::Example;
+    use render::{Render, Simulation};
+
+    const INPUT: &str = Solver::EXAMPLE;
 
     #[test]
     fn test_part1() {
@@ -218,4 +189,119 @@ mod tests {
         let parsed = Solver::parse_input(INPUT);
         assert_eq!(Solver::solve_part2(&parsed), 6);
     }
+
+    #[test]
+    fn test_render() {
+        let picture = Solver::render(&Solver::parse_input(INPUT));
+        assert_eq!(picture.to_string(), INPUT);
+        let tiles = picture.tiles().iter();
+        assert_eq!(tiles.filter(|(_, t)| t.background.is_some()).count(), 41);
+    }
+
+    #[test]
+    fn test_states() {
+        let parsed = Solver::parse_input(INPUT);
+        let (pos, dir, visited) = Solver::states(&parsed).last().unwrap();
+        assert_eq!(visited.iter().filter(|(_, v)| **v).count(), 41);
+        // the guard leaves through the bottom edge
+        assert_eq!(pos.0, parsed.0.rows() - 1);
+        let picture = Solver::draw(&parsed, &(pos, dir, visited));
+        assert_eq!(picture.tiles()[pos].glyph, 'v');
+    }
+
+    #[test]
+    fn test_loops() {
+        let parsed = Solver::parse_input(".#...\n....#\n#^...\n...#.\n");
+        assert_eq!(Solver::solve_part1(&parsed), 6);
+        let parsed = Solver::parse_input(".#.\n#^#\n.#.\n");
+        assert_eq!(Solver::solve_part1(&parsed), 1);
+        assert_eq!(Solver::solve_part2(&parsed), 0);
+    }
+
+    #[test]
+    fn test_input_variants() {
+        for input in parsing::variants(INPUT) {
+            let parsed = Solver::parse_input(&input);
+            assert_eq!(Solver::solve_part1(&parsed), 41);
+        }
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use std::collections::HashSet;
+
+    use crate::Solver;
+    use aoc_traits::AdventOfCodeDay;
+    use proptest::prelude::*;
+
+    /// The positions the guard walks over, or `None` if it never leaves.
+    fn walk(map: &[Vec<u8>], start: (usize, usize)) -> Option<HashSet<(isize, isize)>> {
+        let (mut row, mut col) = (start.0 as isize, start.1 as isize);
+        let (mut dr, mut dc) = match map[start.0][start.1] {
+            b'^' => (-1, 0),
+            b'v' => (1, 0),
+            b'<' => (0, -1),
+            _ => (0, 1),
+        };
+        let mut seen = HashSet::new();
+        while seen.insert((row, col, dr, dc)) {
+            let (next_row, next_col) = (row + dr, col + dc);
+            let cell = usize::try_from(next_row)
+                .ok()
+                .and_then(|r| map.get(r)?.get(usize::try_from(next_col).ok()?));
+            match cell {
+                None => return Some(seen.into_iter().map(|(r, c, _, _)| (r, c)).collect()),
+                Some(b'#') => (dr, dc) = (dc, -dr),
+                Some(_) => (row, col) = (next_row, next_col),
+            }
+        }
+        None
+    }
+
+    /// Tries an obstruction on every free position but the guard's.
+    fn reference_part2(map: &[Vec<u8>], start: (usize, usize)) -> u32 {
+        let mut count = 0;
+        for row in 0..map.len() {
+            for col in 0..map[0].len() {
+                if map[row][col] == b'.' {
+                    let mut map = map.to_vec();
+                    map[row][col] = b'#';
+                    count += walk(&map, start).is_none() as u32;
+                }
+            }
+        }
+        count
+    }
+
+    /// Maps the guard leaves, as in the puzzle.
+    fn map() -> impl Strategy<Value = (Vec<Vec<u8>>, (usize, usize))> {
+        (1..12usize, 1..12usize)
+            .prop_flat_map(|(rows, cols)| {
+                let cell = prop::sample::select(&b"....#"[..]);
+                (
+                    prop::collection::vec(prop::collection::vec(cell, cols), rows),
+                    (0..rows, 0..cols),
+                    prop::sample::select(&b"^v<>"[..]),
+                )
+            })
+            .prop_map(|(mut map, start, guard)| {
+                map[start.0][start.1] = guard;
+                (map, start)
+            })
+            .prop_filter("the guard has to leave", |(map, start)| {
+                walk(map, *start).is_some()
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn matches_reference((map, start) in map()) {
+            let input = map.iter().map(|row| String::from_utf8_lossy(row) + "\n").collect::<String>();
+            let parsed = Solver::parse_input(&input);
+            let visited = walk(&map, start).unwrap().len() as u32;
+            prop_assert_eq!(Solver::solve_part1(&parsed), visited);
+            prop_assert_eq!(Solver::solve_part2(&parsed), reference_part2(&map, start));
+        }
+    }
 }
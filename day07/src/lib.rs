@@ -1,4 +1,5 @@
 use aoc_traits::AdventOfCodeDay;
+use parsing::{Example, ParseError, Source, TryParse};
 
 #[derive(Debug, Clone, Copy)]
 enum Op1 {
@@ -58,16 +59,7 @@ impl AdventOfCodeDay for Solver {
     type Part2Output = u64;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        input
-            .lines()
-            .map(|line| {
-                let (res, terms) = line.split_once(": ").unwrap();
-                (
-                    res.parse().unwrap(),
-                    terms.split(' ').map(|t| t.parse().unwrap()).collect(),
-                )
-            })
-            .collect()
+        Self::try_parse_input(input).unwrap_or_else(|e| panic!("{e}"))
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
@@ -102,12 +94,26 @@ impl AdventOfCodeDay for Solver {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::Solver;
-    use aoc_traits::AdventOfCodeDay;
+impl TryParse for Solver {
+    fn try_parse_input(input: &str) -> Result<Self::ParsedInput<'_>, ParseError> {
+        let src = Source::new(input);
+        src.lines()
+            .map(|line| {
+                let (res, terms) = src.split_once(line, ": ")?;
+                Ok((
+                    src.number(res)?,
+                    terms
+                        .split(' ')
+                        .map(|t| src.number(t))
+                        .collect::<Result<_, _>>()?,
+                ))
+            })
+            .collect()
+    }
+}
 
-    const INPUT: &str = "190: 10 19
+impl Example for Solver {
+    const EXAMPLE: &'static str = "190: 10 19
 3267: 81 40 27
 83: 17 5
 156: 15 6
@@ -117,6 +123,15 @@ mod tests {
 21037: 9 7 18 13
 292: 11 6 16 20
 ";
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Solver;
+    use aoc_traits::AdventOfCodeDay;
+    use parsing::Example;
+
+    const INPUT: &str = Solver::EXAMPLE;
 
     #[test]
     fn test_part1() {
@@ -129,4 +144,82 @@ mod tests {
         let parsed = Solver::parse_input(INPUT);
         assert_eq!(Solver::solve_part2(&parsed), 11387);
     }
+
+    #[test]
+    fn test_input_variants() {
+        for input in parsing::variants(INPUT) {
+            let parsed = Solver::parse_input(&input);
+            assert_eq!(Solver::solve_part1(&parsed), 3749);
+        }
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use crate::Solver;
+    use aoc_traits::AdventOfCodeDay;
+    use proptest::prelude::*;
+
+    type Equation = (u64, Vec<u64>);
+
+    fn apply(op: u8, a: u64, b: u64) -> u64 {
+        match op {
+            0 => a + b,
+            1 => a * b,
+            _ => format!("{a}{b}").parse().unwrap(),
+        }
+    }
+
+    /// Sums the results that some combination of the first `ops` operators, evaluated left to
+    /// right, makes from the terms.
+    fn reference(ops: u8, equations: &[Equation]) -> u64 {
+        let solvable = |(result, terms): &&Equation| {
+            (0..(ops as usize).pow(terms.len() as u32 - 1)).any(|mut combination| {
+                let mut acc = terms[0];
+                for term in &terms[1..] {
+                    acc = apply((combination % ops as usize) as u8, acc, *term);
+                    combination /= ops as usize;
+                }
+                acc == *result
+            })
+        };
+        equations
+            .iter()
+            .filter(solvable)
+            .map(|(result, _)| result)
+            .sum()
+    }
+
+    /// Results are made from the terms with random operators, and some are then off by one.
+    fn equations() -> impl Strategy<Value = Vec<Equation>> {
+        let equation = (
+            prop::collection::vec(1..=20u64, 1..=6),
+            prop::collection::vec(0..3u8, 5),
+            any::<bool>(),
+        )
+            .prop_map(|(terms, ops, off)| {
+                let mut result = terms[0];
+                for (op, term) in ops.iter().zip(&terms[1..]) {
+                    result = apply(*op, result, *term);
+                }
+                (result + off as u64, terms)
+            });
+        prop::collection::vec(equation, 1..20)
+    }
+
+    proptest! {
+        #[test]
+        fn matches_reference(equations in equations()) {
+            let input = equations
+                .iter()
+                .map(|(result, terms)| {
+                    let terms = terms.iter().map(u64::to_string).collect::<Vec<_>>();
+                    format!("{result}: {}\n", terms.join(" "))
+                })
+                .collect::<String>();
+            let parsed = Solver::parse_input(&input);
+            prop_assert_eq!(Solver::solve_part1(&parsed), reference(2, &equations));
+            prop_assert_eq!(Solver::solve_part2(&parsed), reference(3, &equations));
+        }
+    }
 }
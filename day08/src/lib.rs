@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use aoc_traits::AdventOfCodeDay;
 use itertools::Itertools;
+use parsing::{Example, ParseError, Source, TryParse};
 
 #[derive(Default)]
 pub struct Solver;
@@ -11,23 +12,7 @@ impl AdventOfCodeDay for Solver {
     type Part2Output = u32;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        let mut rows = 0;
-        let mut cols = 0;
-        let mut antennas = HashMap::<u8, Vec<_>>::new();
-        for (row, line) in input.lines().enumerate() {
-            rows += 1;
-            let line = line.as_bytes();
-            cols = line.len();
-            for (col, c) in line.iter().enumerate() {
-                if *c != b'.' {
-                    antennas
-                        .entry(*c)
-                        .or_insert(Vec::new())
-                        .push((row as i32, col as i32));
-                }
-            }
-        }
-        (rows, cols, antennas)
+        Self::try_parse_input(input).unwrap_or_else(|e| panic!("{e}"))
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
@@ -111,12 +96,30 @@ impl AdventOfCodeDay for Solver {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::Solver;
-    use aoc_traits::AdventOfCodeDay;
+impl TryParse for Solver {
+    fn try_parse_input(input: &str) -> Result<Self::ParsedInput<'_>, ParseError> {
+        let mut rows = 0;
+        let mut cols = 0;
+        let mut antennas = HashMap::<u8, Vec<_>>::new();
+        for (row, line) in Source::new(input).lines().enumerate() {
+            rows += 1;
+            let line = line.as_bytes();
+            cols = line.len();
+            for (col, c) in line.iter().enumerate() {
+                if *c != b'.' {
+                    antennas
+                        .entry(*c)
+                        .or_insert(Vec::new())
+                        .push((row as i32, col as i32));
+                }
+            }
+        }
+        Ok((rows, cols, antennas))
+    }
+}
 
-    const INPUT: &str = "............
+impl Example for Solver {
+    const EXAMPLE: &'static str = "............
 ........0...
 .....0......
 .......0....
@@ -129,6 +132,15 @@ mod tests {
 ............
 ............
 ";
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Solver;
+    use aoc_traits::AdventOfCodeDay;
+    use parsing::Example;
+
+    const INPUT: &str = Solver::EXAMPLE;
 
     #[test]
     fn test_part1() {
@@ -141,4 +153,106 @@ mod tests {
         let parsed = Solver::parse_input(INPUT);
         assert_eq!(Solver::solve_part2(&parsed), 34);
     }
+
+    #[test]
+    fn test_input_variants() {
+        for input in parsing::variants(INPUT) {
+            let parsed = Solver::parse_input(&input);
+            assert_eq!(Solver::solve_part1(&parsed), 14);
+        }
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use crate::Solver;
+    use aoc_traits::AdventOfCodeDay;
+    use proptest::prelude::*;
+
+    fn gcd(a: i32, b: i32) -> i32 {
+        if b == 0 {
+            a.abs()
+        } else {
+            gcd(b, a % b)
+        }
+    }
+
+    /// Pairs of different antennas with the same frequency, both ways round.
+    fn pairs(grid: &[Vec<u8>]) -> Vec<((i32, i32), (i32, i32))> {
+        let antennas = grid
+            .iter()
+            .enumerate()
+            .flat_map(|(r, row)| row.iter().enumerate().map(move |(c, f)| (*f, r, c)))
+            .filter(|(f, _, _)| *f != b'.')
+            .collect::<Vec<_>>();
+        let mut pairs = vec![];
+        for (f, r, c) in &antennas {
+            for (g, s, d) in &antennas {
+                if f == g && (r, c) != (s, d) {
+                    pairs.push(((*r as i32, *c as i32), (*s as i32, *d as i32)));
+                }
+            }
+        }
+        pairs
+    }
+
+    /// Counts the positions for which `antinode` holds with any pair of antennas.
+    fn count(
+        grid: &[Vec<u8>],
+        antinode: impl Fn((i32, i32), (i32, i32), (i32, i32)) -> bool,
+    ) -> u32 {
+        let pairs = pairs(grid);
+        let mut count = 0;
+        for r in 0..grid.len() as i32 {
+            for c in 0..grid[0].len() as i32 {
+                if pairs.iter().any(|(a, b)| antinode((r, c), *a, *b)) {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    /// Square maps, as in the puzzle. The solver only visits multiples of the offset between two
+    /// antennas, which finds every position in line with them as long as the offset's components
+    /// have no common divisor. The puzzle inputs are like that, so these are too.
+    fn grid() -> impl Strategy<Value = Vec<Vec<u8>>> {
+        (1..12usize)
+            .prop_flat_map(|size| {
+                let antennas = prop::collection::vec((0..size, 0..size), 0..4);
+                (Just(size), prop::collection::vec(antennas, 1..=3))
+            })
+            .prop_filter_map("offsets have to be coprime", |(size, frequencies)| {
+                let mut grid = vec![vec![b'.'; size]; size];
+                for (antennas, frequency) in frequencies.iter().zip(b"aA0") {
+                    for (r, c) in antennas {
+                        if grid[*r][*c] != b'.' {
+                            return None;
+                        }
+                        grid[*r][*c] = *frequency;
+                    }
+                }
+                pairs(&grid)
+                    .iter()
+                    .all(|(a, b)| gcd(b.0 - a.0, b.1 - a.1) == 1)
+                    .then_some(grid)
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn matches_reference(grid in grid()) {
+            let input = grid.iter().map(|row| String::from_utf8_lossy(row) + "\n").collect::<String>();
+            let parsed = Solver::parse_input(&input);
+            // in line with both antennas and twice as far from one as from the other
+            let part1 = count(&grid, |p, a, b| {
+                p.0 - a.0 == 2 * (p.0 - b.0) && p.1 - a.1 == 2 * (p.1 - b.1)
+            });
+            let part2 = count(&grid, |p, a, b| {
+                (b.0 - a.0) * (p.1 - a.1) == (b.1 - a.1) * (p.0 - a.0)
+            });
+            prop_assert_eq!(Solver::solve_part1(&parsed), part1);
+            prop_assert_eq!(Solver::solve_part2(&parsed), part2);
+        }
+    }
 }
@@ -1,4 +1,5 @@
 use aoc_traits::AdventOfCodeDay;
+use parsing::{Example, ParseError, Source, TryParse};
 
 #[derive(Debug, Clone, Copy)]
 enum Space {
@@ -14,7 +15,7 @@ impl AdventOfCodeDay for Solver {
     type Part2Output = u64;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        input.as_bytes().iter().map(|d| d - 48).collect()
+        Self::try_parse_input(input).unwrap_or_else(|e| panic!("{e}"))
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
@@ -109,12 +110,30 @@ impl AdventOfCodeDay for Solver {
     }
 }
 
+impl TryParse for Solver {
+    fn try_parse_input(input: &str) -> Result<Self::ParsedInput<'_>, ParseError> {
+        input
+            .trim_end()
+            .char_indices()
+            .map(|(i, c)| match c.to_digit(10) {
+                Some(d) => Ok(d as u8),
+                None => Err(Source::new(input).error_at(input, i, "expected a digit")),
+            })
+            .collect()
+    }
+}
+
+impl Example for Solver {
+    const EXAMPLE: &'static str = "2333133121414131402";
+}
+
 #[cfg(test)]
 mod tests {
     use crate::Solver;
     use aoc_traits::AdventOfCodeDay;
+    use parsing::Example;
 
-    const INPUT: &str = "2333133121414131402";
+    const INPUT: &str = Solver::EXAMPLE;
 
     #[test]
     fn test_part1() {
@@ -127,4 +146,89 @@ mod tests {
         let parsed = Solver::parse_input(INPUT);
         assert_eq!(Solver::solve_part2(&parsed), 2858);
     }
+
+    #[test]
+    fn test_input_variants() {
+        for input in parsing::variants(INPUT) {
+            let parsed = Solver::parse_input(&input);
+            assert_eq!(Solver::solve_part1(&parsed), 1928);
+        }
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use crate::Solver;
+    use aoc_traits::AdventOfCodeDay;
+    use proptest::prelude::*;
+
+    /// One entry per block, the file's id or `None` for free space.
+    fn blocks(disk_map: &[u8]) -> Vec<Option<u64>> {
+        let mut blocks = vec![];
+        for (i, len) in disk_map.iter().enumerate() {
+            let block = (i % 2 == 0).then_some(i as u64 / 2);
+            blocks.extend(std::iter::repeat_n(block, *len as usize));
+        }
+        blocks
+    }
+
+    fn checksum(blocks: &[Option<u64>]) -> u64 {
+        blocks
+            .iter()
+            .enumerate()
+            .map(|(i, id)| i as u64 * id.unwrap_or(0))
+            .sum()
+    }
+
+    /// Moves the last file block to the first free block until there are no gaps.
+    fn reference_part1(disk_map: &[u8]) -> u64 {
+        let mut blocks = blocks(disk_map);
+        loop {
+            let free = blocks.iter().position(Option::is_none);
+            let last = blocks.iter().rposition(Option::is_some);
+            match (free, last) {
+                (Some(free), Some(last)) if free < last => blocks.swap(free, last),
+                _ => break,
+            }
+        }
+        checksum(&blocks)
+    }
+
+    /// Moves each file once, by decreasing id, to the first span of free blocks left of it that is
+    /// long enough.
+    fn reference_part2(disk_map: &[u8]) -> u64 {
+        let mut blocks = blocks(disk_map);
+        for id in (0..disk_map.len() as u64 / 2 + 1).rev() {
+            let start = blocks.iter().position(|b| *b == Some(id)).unwrap();
+            let len = blocks.iter().filter(|b| **b == Some(id)).count();
+            let free = (0..start).find(|i| blocks[*i..*i + len].iter().all(Option::is_none));
+            if let Some(free) = free {
+                for i in 0..len {
+                    blocks.swap(free + i, start + i);
+                }
+            }
+        }
+        checksum(&blocks)
+    }
+
+    /// Files of one to nine blocks with up to nine free blocks between them.
+    fn disk_map() -> impl Strategy<Value = Vec<u8>> {
+        (1..=9u8, prop::collection::vec((0..=9u8, 1..=9u8), 0..20)).prop_map(|(first, rest)| {
+            let mut disk_map = vec![first];
+            for (free, file) in rest {
+                disk_map.extend([free, file]);
+            }
+            disk_map
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn matches_reference(disk_map in disk_map()) {
+            let input = disk_map.iter().map(|d| (b'0' + d) as char).collect::<String>() + "\n";
+            let parsed = Solver::parse_input(&input);
+            prop_assert_eq!(Solver::solve_part1(&parsed), reference_part1(&disk_map));
+            prop_assert_eq!(Solver::solve_part2(&parsed), reference_part2(&disk_map));
+        }
+    }
 }
@@ -1,85 +1,55 @@
 use std::collections::HashSet;
 
 use aoc_traits::AdventOfCodeDay;
+use grid::{Grid, Position};
+use parsing::{Example, ParseError, Source, TryParse};
 
-fn score1(map: &[Vec<u8>], row: usize, col: usize, heads: &mut HashSet<(usize, usize)>) {
-    let cur = map[row][col];
-    if cur == 9 {
-        heads.insert((row, col));
+/// The positions next to `pos` one higher than it.
+fn uphill(map: &Grid<u8>, pos: Position) -> impl Iterator<Item = Position> + '_ {
+    map.neighbors4(pos)
+        .filter(move |next| map[*next] == map[pos] + 1)
+}
+
+fn score1(map: &Grid<u8>, pos: Position, heads: &mut HashSet<Position>) {
+    if map[pos] == 9 {
+        heads.insert(pos);
         return;
     }
 
-    if map[row - 1][col] == cur + 1 {
-        score1(map, row - 1, col, heads);
-    }
-    if map[row + 1][col] == cur + 1 {
-        score1(map, row + 1, col, heads);
-    }
-    if map[row][col - 1] == cur + 1 {
-        score1(map, row, col - 1, heads);
-    }
-    if map[row][col + 1] == cur + 1 {
-        score1(map, row, col + 1, heads);
+    for next in uphill(map, pos) {
+        score1(map, next, heads);
     }
 }
 
-fn score2(map: &[Vec<u8>], row: usize, col: usize, heads: &mut usize) {
-    let cur = map[row][col];
-    if cur == 9 {
+fn score2(map: &Grid<u8>, pos: Position, heads: &mut usize) {
+    if map[pos] == 9 {
         *heads += 1;
         return;
     }
 
-    if map[row - 1][col] == cur + 1 {
-        score2(map, row - 1, col, heads);
-    }
-    if map[row + 1][col] == cur + 1 {
-        score2(map, row + 1, col, heads);
-    }
-    if map[row][col - 1] == cur + 1 {
-        score2(map, row, col - 1, heads);
-    }
-    if map[row][col + 1] == cur + 1 {
-        score2(map, row, col + 1, heads);
+    for next in uphill(map, pos) {
+        score2(map, next, heads);
     }
 }
 
 #[derive(Default)]
 pub struct Solver;
 impl AdventOfCodeDay for Solver {
-    type ParsedInput<'a> = Vec<Vec<u8>>;
+    type ParsedInput<'a> = Grid<u8>;
     type Part1Output = usize;
     type Part2Output = usize;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        let map = input
-            .lines()
-            .map(|line| {
-                let line = line.chars().map(|c| c as u8 - 48).collect::<Vec<u8>>();
-                let mut padded = vec![0];
-                padded.extend(line);
-                padded.push(0);
-                padded
-            })
-            .collect::<Vec<Vec<u8>>>();
-        let mut padded = vec![vec![0; map[0].len()]];
-        padded.extend(map);
-        padded.push(vec![0; padded[0].len()]);
-        padded
+        Self::try_parse_input(input).unwrap_or_else(|e| panic!("{e}"))
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
-        let rows = input.len();
-        let cols = input[0].len();
-
         let mut total_score = 0;
-        for row in 1..rows - 1 {
-            for col in 1..cols - 1 {
-                if input[row][col] == 0 {
-                    let mut heads = HashSet::new();
-                    score1(input, row, col, &mut heads);
-                    total_score += heads.len();
-                }
+        for (pos, height) in input.iter() {
+            if *height == 0 {
+                let mut heads = HashSet::new();
+                score1(input, pos, &mut heads);
+                total_score += heads.len();
             }
         }
 
@@ -87,17 +57,12 @@ impl AdventOfCodeDay for Solver {
     }
 
     fn solve_part2(input: &Self::ParsedInput<'_>) -> Self::Part2Output {
-        let rows = input.len();
-        let cols = input[0].len();
-
         let mut total_score = 0;
-        for row in 1..rows - 1 {
-            for col in 1..cols - 1 {
-                if input[row][col] == 0 {
-                    let mut heads = 0;
-                    score2(input, row, col, &mut heads);
-                    total_score += heads
-                }
+        for (pos, height) in input.iter() {
+            if *height == 0 {
+                let mut heads = 0;
+                score2(input, pos, &mut heads);
+                total_score += heads
             }
         }
 
@@ -105,12 +70,17 @@ impl AdventOfCodeDay for Solver {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::Solver;
-    use aoc_traits::AdventOfCodeDay;
+impl TryParse for Solver {
+    fn try_parse_input(input: &str) -> Result<Self::ParsedInput<'_>, ParseError> {
+        Grid::parse(&Source::new(input), input, |_, c| match c.to_digit(10) {
+            Some(height) => Ok(height as u8),
+            None => Err("expected a height from 0 to 9"),
+        })
+    }
+}
 
-    const INPUT: &str = "89010123
+impl Example for Solver {
+    const EXAMPLE: &'static str = "89010123
 78121874
 87430965
 96549874
@@ -119,6 +89,15 @@ mod tests {
 01329801
 10456732
 ";
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Solver;
+    use aoc_traits::AdventOfCodeDay;
+    use parsing::Example;
+
+    const INPUT: &str = Solver::EXAMPLE;
 
     #[test]
     fn test_part1() {
@@ -131,4 +110,104 @@ mod tests {
         let parsed = Solver::parse_input(INPUT);
         assert_eq!(Solver::solve_part2(&parsed), 81);
     }
+
+    #[test]
+    fn test_input_variants() {
+        for input in parsing::variants(INPUT) {
+            let parsed = Solver::parse_input(&input);
+            assert_eq!(Solver::solve_part1(&parsed), 36);
+        }
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use std::collections::HashSet;
+
+    use crate::Solver;
+    use aoc_traits::AdventOfCodeDay;
+    use proptest::prelude::*;
+
+    /// The positions one higher than `(r, c)` next to it.
+    fn uphill(map: &[Vec<u8>], (r, c): (usize, usize)) -> Vec<(usize, usize)> {
+        [
+            (r.wrapping_sub(1), c),
+            (r + 1, c),
+            (r, c.wrapping_sub(1)),
+            (r, c + 1),
+        ]
+        .into_iter()
+        .filter(|(s, d)| map.get(*s).and_then(|row| row.get(*d)) == Some(&(map[r][c] + 1)))
+        .collect()
+    }
+
+    fn positions(map: &[Vec<u8>], height: u8) -> Vec<(usize, usize)> {
+        (0..map.len())
+            .flat_map(|r| (0..map[0].len()).map(move |c| (r, c)))
+            .filter(|(r, c)| map[*r][*c] == height)
+            .collect()
+    }
+
+    /// Widens the set of reachable positions one height at a time.
+    fn reference_part1(map: &[Vec<u8>]) -> usize {
+        positions(map, 0)
+            .into_iter()
+            .map(|head| {
+                let mut reachable = HashSet::from([head]);
+                for _ in 0..9 {
+                    reachable = reachable.iter().flat_map(|p| uphill(map, *p)).collect();
+                }
+                reachable.len()
+            })
+            .sum()
+    }
+
+    /// Counts the trails from every position to a summit, from the summits down.
+    fn reference_part2(map: &[Vec<u8>]) -> usize {
+        let mut trails = vec![vec![0; map[0].len()]; map.len()];
+        for (r, c) in positions(map, 9) {
+            trails[r][c] = 1;
+        }
+        for height in (0..9).rev() {
+            for (r, c) in positions(map, height) {
+                trails[r][c] = uphill(map, (r, c))
+                    .iter()
+                    .map(|(s, d)| trails[*s][*d])
+                    .sum();
+            }
+        }
+        positions(map, 0).iter().map(|(r, c)| trails[*r][*c]).sum()
+    }
+
+    /// Mostly slopes, so there are trails, with random heights in between.
+    fn map() -> impl Strategy<Value = Vec<Vec<u8>>> {
+        (1..12usize, 1..12usize, 0..10usize)
+            .prop_flat_map(|(rows, cols, shift)| {
+                let cell = prop::option::weighted(0.25, 0..=9u8);
+                let cells = prop::collection::vec(prop::collection::vec(cell, cols), rows);
+                (Just(shift), cells)
+            })
+            .prop_map(|(shift, cells)| {
+                cells
+                    .into_iter()
+                    .enumerate()
+                    .map(|(r, row)| {
+                        row.into_iter()
+                            .enumerate()
+                            .map(|(c, height)| height.unwrap_or(((r + c + shift) % 10) as u8))
+                            .collect()
+                    })
+                    .collect()
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn matches_reference(map in map()) {
+            let input = map.iter().map(|row| row.iter().map(|h| (b'0' + h) as char).collect::<String>() + "\n").collect::<String>();
+            let parsed = Solver::parse_input(&input);
+            prop_assert_eq!(Solver::solve_part1(&parsed), reference_part1(&map));
+            prop_assert_eq!(Solver::solve_part2(&parsed), reference_part2(&map));
+        }
+    }
 }
@@ -1,22 +1,38 @@
+use std::collections::HashMap;
+
 use aoc_traits::AdventOfCodeDay;
-use memoize::memoize;
+use parsing::{Example, ParseError, Source, TryParse};
 
-#[memoize]
-fn apply(stone: u64, depth: usize) -> usize {
+/// Number of stones `stone` turns into after `depth` blinks. The cache belongs to one solve, so
+/// solvers running concurrently do not share or flush each other's entries.
+fn apply(stone: u64, depth: usize, cache: &mut HashMap<(u64, usize), usize>) -> usize {
     if depth == 0 {
         return 1;
     }
+    if let Some(count) = cache.get(&(stone, depth)) {
+        return *count;
+    }
 
-    if stone == 0 {
-        apply(stone + 1, depth - 1)
+    let count = if stone == 0 {
+        apply(stone + 1, depth - 1, cache)
     } else if (stone.ilog10() + 1) % 2 == 0 {
         let factor = 10u64.pow((stone.ilog10() + 1) / 2);
         let left = stone / factor;
         let right = stone % factor;
-        apply(left, depth - 1) + apply(right, depth - 1)
+        apply(left, depth - 1, cache) + apply(right, depth - 1, cache)
     } else {
-        apply(stone * 2024, depth - 1)
-    }
+        apply(stone * 2024, depth - 1, cache)
+    };
+    cache.insert((stone, depth), count);
+    count
+}
+
+fn blink(stones: &[u64], depth: usize) -> usize {
+    let mut cache = HashMap::new();
+    stones
+        .iter()
+        .map(|stone| apply(*stone, depth, &mut cache))
+        .sum()
 }
 
 #[derive(Default)]
@@ -27,30 +43,38 @@ impl AdventOfCodeDay for Solver {
     type Part2Output = usize;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        input.split(' ').map(|s| s.parse().unwrap()).collect()
+        Self::try_parse_input(input).unwrap_or_else(|e| panic!("{e}"))
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
-        let res = input.iter().map(|stone| apply(*stone, 25)).sum();
-        memoized_flush_apply();
-        res
+        blink(input, 25)
     }
 
     fn solve_part2(input: &Self::ParsedInput<'_>) -> Self::Part2Output {
-        let res = input.iter().map(|stone| apply(*stone, 75)).sum();
-        memoized_flush_apply();
-        res
+        blink(input, 75)
+    }
+}
+
+impl TryParse for Solver {
+    fn try_parse_input(input: &str) -> Result<Self::ParsedInput<'_>, ParseError> {
+        let src = Source::new(input);
+        input.split_whitespace().map(|s| src.number(s)).collect()
     }
 }
 
+impl Example for Solver {
+    const EXAMPLE: &'static str = "77 515 6779622 6 91370 959685 0 9861";
+}
+
 #[cfg(test)]
 mod tests {
     use std::time::Instant;
 
     use crate::Solver;
     use aoc_traits::AdventOfCodeDay;
+    use parsing::Example;
 
-    const INPUT: &str = "77 515 6779622 6 91370 959685 0 9861";
+    const INPUT: &str = Solver::EXAMPLE;
 
     #[test]
     fn test_part1() {
@@ -65,4 +89,81 @@ mod tests {
         assert_eq!(Solver::solve_part2(&parsed), 223767210249237);
         println!("took {}", start.elapsed().as_micros());
     }
+
+    #[test]
+    fn test_input_variants() {
+        for input in parsing::variants(INPUT) {
+            let parsed = Solver::parse_input(&input);
+            assert_eq!(Solver::solve_part1(&parsed), 187738);
+        }
+    }
+
+    #[test]
+    fn test_concurrent() {
+        let parsed = Solver::parse_input(INPUT);
+        std::thread::scope(|s| {
+            let solvers = (0..4)
+                .map(|_| s.spawn(|| (Solver::solve_part1(&parsed), Solver::solve_part2(&parsed))))
+                .collect::<Vec<_>>();
+            for solver in solvers {
+                assert_eq!(solver.join().unwrap(), (187738, 223767210249237));
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use crate::{blink, Solver};
+    use aoc_traits::AdventOfCodeDay;
+    use proptest::prelude::*;
+
+    /// Replaces every stone in the line, one blink at a time.
+    fn simulate(stones: &[u64], blinks: usize) -> Vec<usize> {
+        let mut stones = stones.to_vec();
+        let mut counts = vec![stones.len()];
+        for _ in 0..blinks {
+            stones = stones
+                .into_iter()
+                .flat_map(|stone| {
+                    let digits = stone.to_string();
+                    if stone == 0 {
+                        vec![1]
+                    } else if digits.len() % 2 == 0 {
+                        let (left, right) = digits.split_at(digits.len() / 2);
+                        vec![left.parse().unwrap(), right.parse().unwrap()]
+                    } else {
+                        vec![stone * 2024]
+                    }
+                })
+                .collect();
+            counts.push(stones.len());
+        }
+        counts
+    }
+
+    fn stones() -> impl Strategy<Value = Vec<u64>> {
+        prop::collection::vec(0..10_000u64, 1..4)
+    }
+
+    proptest! {
+        // every case blinks hundreds of thousands of stones
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn part1_matches_reference(stones in stones()) {
+            let input = stones.iter().map(u64::to_string).collect::<Vec<_>>().join(" ");
+            let parsed = Solver::parse_input(&input);
+            prop_assert_eq!(Solver::solve_part1(&parsed), simulate(&stones, 25)[25]);
+        }
+
+        /// Part 2 is the same count after 75 blinks, far too many to simulate, so this checks
+        /// the counts on the way to part 1's instead.
+        #[test]
+        fn blinks_match_reference(stones in stones()) {
+            for (blinks, count) in simulate(&stones, 25).into_iter().enumerate() {
+                prop_assert_eq!(blink(&stones, blinks), count);
+            }
+        }
+    }
 }
@@ -1,47 +1,29 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, convert::Infallible};
 
 use aoc_traits::AdventOfCodeDay;
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Dir {
-    Left,
-    Right,
-    Up,
-    Down,
-}
-
-impl Dir {
-    pub fn offset(&self, row: usize, col: usize) -> (usize, usize) {
-        match self {
-            Dir::Left => (row, col - 1),
-            Dir::Right => (row, col + 1),
-            Dir::Up => (row - 1, col),
-            Dir::Down => (row + 1, col),
-        }
-    }
-}
+use grid::{Direction, Grid, Position};
+use parsing::{Example, ParseError, Source, TryParse};
+use render::{Color, Picture, Render, Tile};
 
 fn area_perimiter(
-    input: &[Vec<u8>],
-    row: usize,
-    col: usize,
+    input: &Grid<u8>,
+    pos: Position,
     plant: u8,
-    visted: &mut HashSet<(usize, usize)>,
+    visted: &mut HashSet<Position>,
 ) -> (usize, usize) {
     let mut area = 1;
     let mut perimiter = 0;
 
-    visted.insert((row, col));
+    visted.insert(pos);
 
-    for dir in [Dir::Left, Dir::Right, Dir::Up, Dir::Down] {
-        let (row, col) = dir.offset(row, col);
-
-        if input[row][col] != plant {
+    for dir in Direction::ALL {
+        let Some(pos) = input.offset(pos, dir.step()).filter(|p| input[*p] == plant) else {
             perimiter += 1;
-        }
+            continue;
+        };
 
-        if !visted.contains(&(row, col)) && input[row][col] == plant {
-            let (a, p) = area_perimiter(input, row, col, input[row][col], visted);
+        if !visted.contains(&pos) {
+            let (a, p) = area_perimiter(input, pos, plant, visted);
             area += a;
             perimiter += p;
         }
@@ -51,31 +33,37 @@ fn area_perimiter(
 }
 
 fn area_region(
-    input: &[Vec<u8>],
-    row: usize,
-    col: usize,
+    input: &Grid<u8>,
+    pos: Position,
     plant: u8,
-    region: &mut [Vec<bool>],
-    visted: &mut HashSet<(usize, usize)>,
+    region: &mut Grid<bool>,
+    visted: &mut HashSet<Position>,
 ) -> usize {
     let mut area = 1;
 
-    visted.insert((row, col));
-    region[row][col] = true;
+    visted.insert(pos);
+    region[pos] = true;
 
-    for dir in [Dir::Left, Dir::Right, Dir::Up, Dir::Down] {
-        let (row, col) = dir.offset(row, col);
-        if !visted.contains(&(row, col)) && input[row][col] == plant {
-            area += area_region(input, row, col, input[row][col], region, visted);
+    for dir in Direction::ALL {
+        let Some(pos) = input.offset(pos, dir.step()) else {
+            continue;
+        };
+        if !visted.contains(&pos) && input[pos] == plant {
+            area += area_region(input, pos, plant, region, visted);
         }
     }
 
     area
 }
 
-fn sides(input: &mut [Vec<bool>]) -> usize {
-    let rows = input.len();
-    let cols = input[0].len();
+fn sides(region: &Grid<bool>) -> usize {
+    let rows = region.rows() as isize;
+    let cols = region.cols() as isize;
+    // everything off the map is outside the region
+    let input = |row: isize, col: isize| {
+        let pos = (usize::try_from(row).ok(), usize::try_from(col).ok());
+        matches!(pos, (Some(row), Some(col)) if region.get((row, col)) == Some(&true))
+    };
 
     let mut top = 0;
     let mut bot = 0;
@@ -85,33 +73,33 @@ fn sides(input: &mut [Vec<bool>]) -> usize {
     let mut in_top_side = false;
     let mut in_bot_side = false;
 
-    for row in 1..rows - 1 {
-        for col in 1..cols - 1 {
+    for row in 0..rows {
+        for col in 0..cols {
             if in_top_side {
-                if !input[row][col + 1] || input[row - 1][col + 1] {
+                if !input(row, col + 1) || input(row - 1, col + 1) {
                     // println!("stopped side {row} {col}");
                     in_top_side = false;
                 }
-            } else if !input[row - 1][col] && input[row][col] {
+            } else if !input(row - 1, col) && input(row, col) {
                 // println!("started side {row} {col}");
                 top += 1;
                 in_top_side = true;
-                if !input[row][col + 1] || input[row - 1][col + 1] {
+                if !input(row, col + 1) || input(row - 1, col + 1) {
                     // println!("stopped side {row} {col}");
                     in_top_side = false;
                 }
             }
 
             if in_bot_side {
-                if !input[row][col + 1] || input[row + 1][col + 1] {
+                if !input(row, col + 1) || input(row + 1, col + 1) {
                     // println!("stopped side {row} {col}");
                     in_bot_side = false;
                 }
-            } else if !input[row + 1][col] && input[row][col] {
+            } else if !input(row + 1, col) && input(row, col) {
                 // println!("started side {row} {col}");
                 bot += 1;
                 in_bot_side = true;
-                if !input[row][col + 1] || input[row + 1][col + 1] {
+                if !input(row, col + 1) || input(row + 1, col + 1) {
                     // println!("stopped side {row} {col}");
                     in_bot_side = false;
                 }
@@ -122,33 +110,33 @@ fn sides(input: &mut [Vec<bool>]) -> usize {
     let mut in_right_side = false;
     let mut in_left_side = false;
 
-    for col in 1..cols - 1 {
-        for row in 1..rows - 1 {
+    for col in 0..cols {
+        for row in 0..rows {
             if in_right_side {
-                if !input[row + 1][col] || input[row + 1][col + 1] {
+                if !input(row + 1, col) || input(row + 1, col + 1) {
                     // println!("stopped side {row} {col}");
                     in_right_side = false;
                 }
-            } else if !input[row][col + 1] && input[row][col] {
+            } else if !input(row, col + 1) && input(row, col) {
                 // println!("started side {row} {col}");
                 right += 1;
                 in_right_side = true;
-                if !input[row + 1][col] || input[row + 1][col + 1] {
+                if !input(row + 1, col) || input(row + 1, col + 1) {
                     // println!("stopped side {row} {col}");
                     in_right_side = false;
                 }
             }
 
             if in_left_side {
-                if !input[row + 1][col] || input[row + 1][col - 1] {
+                if !input(row + 1, col) || input(row + 1, col - 1) {
                     // println!("stopped side {row} {col}");
                     in_left_side = false;
                 }
-            } else if !input[row][col - 1] && input[row][col] {
+            } else if !input(row, col - 1) && input(row, col) {
                 // println!("started side {row} {col}");
                 left += 1;
                 in_left_side = true;
-                if !input[row + 1][col] || input[row + 1][col - 1] {
+                if !input(row + 1, col) || input(row + 1, col - 1) {
                     // println!("stopped side {row} {col}");
                     in_left_side = false;
                 }
@@ -162,40 +150,22 @@ fn sides(input: &mut [Vec<bool>]) -> usize {
 #[derive(Default)]
 pub struct Solver;
 impl AdventOfCodeDay for Solver {
-    type ParsedInput<'a> = Vec<Vec<u8>>;
+    type ParsedInput<'a> = Grid<u8>;
     type Part1Output = usize;
     type Part2Output = usize;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        let map = input
-            .lines()
-            .map(|line| {
-                let line = line.chars().map(|c| c as u8).collect::<Vec<u8>>();
-                let mut padded = vec![0];
-                padded.extend(line);
-                padded.push(0);
-                padded
-            })
-            .collect::<Vec<Vec<u8>>>();
-        let mut padded = vec![vec![0; map[0].len()]];
-        padded.extend(map);
-        padded.push(vec![0; padded[0].len()]);
-        padded
+        Self::try_parse_input(input).unwrap_or_else(|e| panic!("{e}"))
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
-        let rows = input.len();
-        let cols = input[0].len();
         let mut visited = HashSet::new();
         let mut price = 0;
 
-        for row in 1..rows - 1 {
-            for col in 1..cols - 1 {
-                if !visited.contains(&(row, col)) {
-                    let (area, perimiter) =
-                        area_perimiter(input, row, col, input[row][col], &mut visited);
-                    price += area * perimiter;
-                }
+        for (pos, plant) in input.iter() {
+            if !visited.contains(&pos) {
+                let (area, perimiter) = area_perimiter(input, pos, *plant, &mut visited);
+                price += area * perimiter;
             }
         }
 
@@ -203,20 +173,15 @@ impl AdventOfCodeDay for Solver {
     }
 
     fn solve_part2(input: &Self::ParsedInput<'_>) -> Self::Part2Output {
-        let rows = input.len();
-        let cols = input[0].len();
         let mut visited = HashSet::new();
         let mut price = 0;
 
-        for row in 1..rows - 1 {
-            for col in 1..cols - 1 {
-                if !visited.contains(&(row, col)) {
-                    let mut region = vec![vec![false; cols]; rows];
-                    let area =
-                        area_region(input, row, col, input[row][col], &mut region, &mut visited);
-                    let sides = sides(&mut region);
-                    price += area * sides;
-                }
+        for (pos, plant) in input.iter() {
+            if !visited.contains(&pos) {
+                let mut region = Grid::new(input.rows(), input.cols(), false);
+                let area = area_region(input, pos, *plant, &mut region, &mut visited);
+                let sides = sides(&region);
+                price += area * sides;
             }
         }
 
@@ -224,16 +189,51 @@ impl AdventOfCodeDay for Solver {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::Solver;
-    use aoc_traits::AdventOfCodeDay;
+impl TryParse for Solver {
+    fn try_parse_input(input: &str) -> Result<Self::ParsedInput<'_>, ParseError> {
+        Grid::parse(&Source::new(input), input, |_, c| {
+            Ok::<_, Infallible>(c as u8)
+        })
+    }
+}
+
+/// Every region in a color of its own.
+impl Render for Solver {
+    fn render(input: &Self::ParsedInput<'_>) -> Picture {
+        let mut picture = Picture::new(input, |plant| Tile::new(*plant as char, Color::BLACK));
+        let mut visited = HashSet::new();
+        let mut regions = 0;
+
+        for (pos, plant) in input.iter() {
+            if !visited.contains(&pos) {
+                let mut region = Grid::new(input.rows(), input.cols(), false);
+                area_region(input, pos, *plant, &mut region, &mut visited);
+                let tiles = region.positions().filter(|pos| region[*pos]);
+                picture.overlay(tiles, Color::palette(regions));
+                regions += 1;
+            }
+        }
+
+        picture
+    }
+}
 
-    const INPUT: &str = "AAAA
+impl Example for Solver {
+    const EXAMPLE: &'static str = "AAAA
 BBCD
 BBCC
 EEEC
 ";
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Solver;
+    use aoc_traits::AdventOfCodeDay;
+    use parsing::Example;
+    use render::Render;
+
+    const INPUT: &str = Solver::EXAMPLE;
 
     #[test]
     fn test_part1() {
@@ -246,4 +246,131 @@ EEEC
         let parsed = Solver::parse_input(INPUT);
         assert_eq!(Solver::solve_part2(&parsed), 80);
     }
+
+    #[test]
+    fn test_render() {
+        let picture = Solver::render(&Solver::parse_input(INPUT));
+        assert_eq!(picture.to_string(), INPUT);
+        let mut colors = picture
+            .tiles()
+            .iter()
+            .map(|(_, t)| t.background.unwrap())
+            .collect::<Vec<_>>();
+        colors.sort_unstable_by_key(|c| (c.r, c.g, c.b));
+        colors.dedup();
+        assert_eq!(colors.len(), 5);
+    }
+
+    #[test]
+    fn test_input_variants() {
+        for input in parsing::variants(INPUT) {
+            let parsed = Solver::parse_input(&input);
+            assert_eq!(Solver::solve_part1(&parsed), 140);
+        }
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use crate::Solver;
+    use aoc_traits::AdventOfCodeDay;
+    use proptest::prelude::*;
+
+    const DIRECTIONS: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+    /// Numbers every plot with its region, filling one region at a time.
+    fn regions(garden: &[Vec<u8>]) -> Vec<Vec<usize>> {
+        let mut regions = vec![vec![usize::MAX; garden[0].len()]; garden.len()];
+        let mut next = 0;
+        for r in 0..garden.len() {
+            for c in 0..garden[0].len() {
+                if regions[r][c] != usize::MAX {
+                    continue;
+                }
+                let mut todo = vec![(r, c)];
+                while let Some((r_, c_)) = todo.pop() {
+                    if regions[r_][c_] != usize::MAX || garden[r_][c_] != garden[r][c] {
+                        continue;
+                    }
+                    regions[r_][c_] = next;
+                    for (dr, dc) in DIRECTIONS {
+                        let (s, d) = (r_.wrapping_add_signed(dr), c_.wrapping_add_signed(dc));
+                        if s < garden.len() && d < garden[0].len() {
+                            todo.push((s, d));
+                        }
+                    }
+                }
+                next += 1;
+            }
+        }
+        regions
+    }
+
+    /// Sums area times `fences` over the regions. `fences` gets the map of regions, a region and
+    /// a plot of it.
+    fn price(
+        garden: &[Vec<u8>],
+        fences: impl Fn(&dyn Fn(isize, isize) -> Option<usize>, usize, (isize, isize)) -> usize,
+    ) -> usize {
+        let regions = regions(garden);
+        let at = |r: isize, c: isize| {
+            let row = regions.get(usize::try_from(r).ok()?)?;
+            row.get(usize::try_from(c).ok()?).copied()
+        };
+        let count = regions.iter().flatten().max().unwrap() + 1;
+        let mut area = vec![0; count];
+        let mut fence = vec![0; count];
+        for r in 0..garden.len() as isize {
+            for c in 0..garden[0].len() as isize {
+                let region = at(r, c).unwrap();
+                area[region] += 1;
+                fence[region] += fences(&at, region, (r, c));
+            }
+        }
+        area.iter().zip(fence).map(|(a, f)| a * f).sum()
+    }
+
+    /// Every side of a plot that does not border its own region needs a fence.
+    fn reference_part1(garden: &[Vec<u8>]) -> usize {
+        price(garden, |at, region, (r, c)| {
+            DIRECTIONS
+                .iter()
+                .filter(|(dr, dc)| at(r + dr, c + dc) != Some(region))
+                .count()
+        })
+    }
+
+    /// Counts the fence pieces that start a side: the ones without a fence facing the same way on
+    /// the plot before them, going along the side.
+    fn reference_part2(garden: &[Vec<u8>]) -> usize {
+        price(garden, |at, region, (r, c)| {
+            let fenced = |r, c, (dr, dc): (isize, isize)| {
+                at(r, c) == Some(region) && at(r + dr, c + dc) != Some(region)
+            };
+            DIRECTIONS
+                .iter()
+                .filter(|(dr, dc)| {
+                    let (br, bc) = (r - dc.abs(), c - dr.abs());
+                    fenced(r, c, (*dr, *dc)) && !fenced(br, bc, (*dr, *dc))
+                })
+                .count()
+        })
+    }
+
+    fn garden() -> impl Strategy<Value = Vec<Vec<u8>>> {
+        (1..12usize, 1..12usize).prop_flat_map(|(rows, cols)| {
+            let plant = prop::sample::select(&b"ABC"[..]);
+            prop::collection::vec(prop::collection::vec(plant, cols), rows)
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn matches_reference(garden in garden()) {
+            let input = garden.iter().map(|row| String::from_utf8_lossy(row) + "\n").collect::<String>();
+            let parsed = Solver::parse_input(&input);
+            prop_assert_eq!(Solver::solve_part1(&parsed), reference_part1(&garden));
+            prop_assert_eq!(Solver::solve_part2(&parsed), reference_part2(&garden));
+        }
+    }
 }
@@ -1,4 +1,5 @@
 use aoc_traits::AdventOfCodeDay;
+use parsing::{Example, ParseError, Source, TryParse};
 
 #[derive(Debug, Clone, Copy)]
 pub struct Claw {
@@ -18,27 +19,7 @@ impl AdventOfCodeDay for Solver {
     // Button B: X+22, Y+67
     // Prize: X=8400, Y=5400
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        input
-            .split("\n\n")
-            .map(|claw| {
-                let vals = claw
-                    .lines()
-                    .map(|line| {
-                        let x_pos = line.chars().position(|c| c == 'X').unwrap();
-                        let y_pos = line.chars().position(|c| c == 'Y').unwrap();
-                        let comma_pos = line.chars().position(|c| c == ',').unwrap();
-                        let v0 = line[x_pos + 2..comma_pos].parse().unwrap();
-                        let v1 = line[y_pos + 2..].parse().unwrap();
-                        (v0, v1)
-                    })
-                    .collect::<Vec<_>>();
-                Claw {
-                    a: vals[0],
-                    b: vals[1],
-                    c: vals[2],
-                }
-            })
-            .collect()
+        Self::try_parse_input(input).unwrap_or_else(|e| panic!("{e}"))
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
@@ -55,7 +36,12 @@ impl AdventOfCodeDay for Solver {
                 let x0 = (b0 * -c1 - b1 * -c0) / (a0 * b1 - a1 * b0);
                 let y0 = (-c0 * a1 - -c1 * a0) / (a0 * b1 - a1 * b0);
 
-                if x0.floor() == x0 && y0.floor() == y0 {
+                let presses = 0.0..=100.0;
+                if presses.contains(&x0)
+                    && presses.contains(&y0)
+                    && x0.floor() == x0
+                    && y0.floor() == y0
+                {
                     x0 as usize * 3 + y0 as usize
                 } else {
                     0
@@ -78,7 +64,7 @@ impl AdventOfCodeDay for Solver {
                 let x0 = (b0 * -c1 - b1 * -c0) / (a0 * b1 - a1 * b0);
                 let y0 = (-c0 * a1 - -c1 * a0) / (a0 * b1 - a1 * b0);
 
-                if x0.floor() == x0 && y0.floor() == y0 {
+                if x0 >= 0.0 && y0 >= 0.0 && x0.floor() == x0 && y0.floor() == y0 {
                     x0 as usize * 3 + y0 as usize
                 } else {
                     0
@@ -88,12 +74,31 @@ impl AdventOfCodeDay for Solver {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::Solver;
-    use aoc_traits::AdventOfCodeDay;
+impl TryParse for Solver {
+    fn try_parse_input(input: &str) -> Result<Self::ParsedInput<'_>, ParseError> {
+        let src = Source::new(input);
+        src.sections()
+            .map(|claw| {
+                let vals = parsing::lines(claw)
+                    .map(|line| {
+                        let (_, coords) = src.split_once(line, "X")?;
+                        let (x, y) = src.split_once(coords, ", Y")?;
+                        let x = src.number(x.trim_start_matches(['+', '=']))?;
+                        let y = src.number(y.trim_start_matches(['+', '=']))?;
+                        Ok((x, y))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let [a, b, c] = vals[..] else {
+                    return Err(src.error(claw, "expected button A, button B and prize lines"));
+                };
+                Ok(Claw { a, b, c })
+            })
+            .collect()
+    }
+}
 
-    const INPUT: &str = "Button A: X+94, Y+34
+impl Example for Solver {
+    const EXAMPLE: &'static str = "Button A: X+94, Y+34
 Button B: X+22, Y+67
 Prize: X=8400, Y=5400
 
@@ -109,6 +114,15 @@ Button A: X+69, Y+23
 Button B: X+27, Y+71
 Prize: X=18641, Y=10279
 ";
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Solver;
+    use aoc_traits::AdventOfCodeDay;
+    use parsing::Example;
+
+    const INPUT: &str = Solver::EXAMPLE;
 
     #[test]
     fn test_part1() {
@@ -121,4 +135,96 @@ Prize: X=18641, Y=10279
         let parsed = Solver::parse_input(INPUT);
         assert_eq!(Solver::solve_part2(&parsed), 875318608908);
     }
+
+    #[test]
+    fn test_input_variants() {
+        for input in parsing::variants(INPUT) {
+            let parsed = Solver::parse_input(&input);
+            assert_eq!(Solver::solve_part1(&parsed), 480);
+        }
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use crate::{Claw, Solver};
+    use aoc_traits::AdventOfCodeDay;
+    use proptest::prelude::*;
+
+    fn render(claws: &[Claw]) -> String {
+        claws
+            .iter()
+            .map(|Claw { a, b, c }| {
+                format!(
+                    "Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}\n",
+                    a.0, a.1, b.0, b.1, c.0, c.1
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Tries every number of presses up to 100 on both buttons.
+    fn reference_part1(claws: &[Claw]) -> usize {
+        claws
+            .iter()
+            .filter_map(|Claw { a, b, c }| {
+                (0..=100)
+                    .flat_map(|i| (0..=100).map(move |j| (i, j)))
+                    .filter(|(i, j)| i * a.0 + j * b.0 == c.0 && i * a.1 + j * b.1 == c.1)
+                    .map(|(i, j)| 3 * i + j)
+                    .min()
+            })
+            .sum()
+    }
+
+    /// Cramer's rule in integers, which cannot lose precision.
+    fn reference_part2(claws: &[Claw]) -> usize {
+        claws
+            .iter()
+            .filter_map(|Claw { a, b, c }| {
+                let [a0, a1, b0, b1] = [a.0, a.1, b.0, b.1].map(|n| n as i128);
+                let [c0, c1] = [c.0, c.1].map(|n| n as i128 + 10000000000000);
+                let det = a0 * b1 - a1 * b0;
+                let i = c0 * b1 - c1 * b0;
+                let j = a0 * c1 - a1 * c0;
+                (i % det == 0 && j % det == 0 && i / det >= 0 && j / det >= 0)
+                    .then(|| (3 * i / det + j / det) as usize)
+            })
+            .sum()
+    }
+
+    /// Like in the puzzle, the buttons never move the claw in the same direction and winnable
+    /// prizes take at most 100 presses of each. The others are a little off a winnable one.
+    fn claws() -> impl Strategy<Value = Vec<Claw>> {
+        let button = (1..100usize, 1..100usize);
+        let claw = (
+            button.clone(),
+            button,
+            0..=100usize,
+            0..=100usize,
+            prop::option::of((0..3usize, 0..3usize)),
+        )
+            .prop_filter("buttons in the same direction", |(a, b, ..)| {
+                a.0 * b.1 != a.1 * b.0
+            })
+            .prop_map(|(a, b, i, j, off)| {
+                let (dx, dy) = off.unwrap_or((0, 0));
+                Claw {
+                    a,
+                    b,
+                    c: (i * a.0 + j * b.0 + dx, i * a.1 + j * b.1 + dy),
+                }
+            });
+        prop::collection::vec(claw, 1..20)
+    }
+
+    proptest! {
+        #[test]
+        fn matches_reference(claws in claws()) {
+            let parsed = Solver::parse_input(&render(&claws));
+            prop_assert_eq!(Solver::solve_part1(&parsed), reference_part1(&claws));
+            prop_assert_eq!(Solver::solve_part2(&parsed), reference_part2(&claws));
+        }
+    }
 }
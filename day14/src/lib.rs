@@ -1,4 +1,10 @@
 use aoc_traits::AdventOfCodeDay;
+use grid::Grid;
+use parsing::{Example, ParseError, Source, TryParse};
+use render::{Color, Picture, Simulation, Tile};
+
+const WIDTH: i64 = 101;
+const HEIGHT: i64 = 103;
 
 #[derive(Debug, Clone, Copy)]
 pub struct Robot {
@@ -16,32 +22,18 @@ impl AdventOfCodeDay for Solver {
     type Part2Output = usize;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        input
-            .lines()
-            .map(|line| {
-                let (pos, vel) = line.split_once(' ').unwrap();
-                let pos_comma = pos.chars().position(|c| c == ',').unwrap();
-                let vel_comma = vel.chars().position(|c| c == ',').unwrap();
-                let x = pos[2..pos_comma].parse().unwrap();
-                let y = pos[pos_comma + 1..].parse().unwrap();
-                let vx = vel[2..vel_comma].parse().unwrap();
-                let vy = vel[vel_comma + 1..].parse().unwrap();
-                Robot { x, y, vx, vy }
-            })
-            .collect()
+        Self::try_parse_input(input).unwrap_or_else(|e| panic!("{e}"))
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
         let mut robots = input.clone();
-        let width = 101;
-        let height = 103;
         let steps = 100;
 
         for robot in robots.iter_mut() {
             robot.x += steps * robot.vx;
-            robot.x = robot.x.rem_euclid(width as i64);
+            robot.x = robot.x.rem_euclid(WIDTH);
             robot.y += steps * robot.vy;
-            robot.y = robot.y.rem_euclid(height as i64);
+            robot.y = robot.y.rem_euclid(HEIGHT);
         }
 
         let mut q1 = 0;
@@ -49,13 +41,13 @@ impl AdventOfCodeDay for Solver {
         let mut q3 = 0;
         let mut q4 = 0;
         for robot in robots.iter() {
-            if robot.x < width as i64 / 2 && robot.y < height as i64 / 2 {
+            if robot.x < WIDTH / 2 && robot.y < HEIGHT / 2 {
                 q1 += 1;
-            } else if robot.x > width as i64 / 2 && robot.y < height as i64 / 2 {
+            } else if robot.x > WIDTH / 2 && robot.y < HEIGHT / 2 {
                 q2 += 1;
-            } else if robot.x > width as i64 / 2 && robot.y > height as i64 / 2 {
+            } else if robot.x > WIDTH / 2 && robot.y > HEIGHT / 2 {
                 q3 += 1;
-            } else if robot.x < width as i64 / 2 && robot.y > height as i64 / 2 {
+            } else if robot.x < WIDTH / 2 && robot.y > HEIGHT / 2 {
                 q4 += 1;
             }
         }
@@ -64,18 +56,16 @@ impl AdventOfCodeDay for Solver {
     }
 
     fn solve_part2(input: &Self::ParsedInput<'_>) -> Self::Part2Output {
-        let width = 101;
-        let height = 103;
         let mut steps = 1;
         let mut robots = input.clone();
-        let mut map = vec![vec![0; width as usize]; height as usize];
+        let mut map = vec![vec![0; WIDTH as usize]; HEIGHT as usize];
 
         loop {
             for robot in robots.iter_mut() {
                 robot.x += robot.vx;
-                robot.x = robot.x.rem_euclid(width as i64);
+                robot.x = robot.x.rem_euclid(WIDTH);
                 robot.y += robot.vy;
-                robot.y = robot.y.rem_euclid(height as i64);
+                robot.y = robot.y.rem_euclid(HEIGHT);
             }
 
             for row in map.iter_mut() {
@@ -107,12 +97,56 @@ impl AdventOfCodeDay for Solver {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::Solver;
-    use aoc_traits::AdventOfCodeDay;
+impl TryParse for Solver {
+    fn try_parse_input(input: &str) -> Result<Self::ParsedInput<'_>, ParseError> {
+        let src = Source::new(input);
+        src.lines()
+            .map(|line| {
+                let (pos, vel) = src.split_once(line, " ")?;
+                let (x, y) = src.split_once(src.strip_prefix(pos, "p=")?, ",")?;
+                let (vx, vy) = src.split_once(src.strip_prefix(vel, "v=")?, ",")?;
+                Ok(Robot {
+                    x: src.number(x)?,
+                    y: src.number(y)?,
+                    vx: src.number(vx)?,
+                    vy: src.number(vy)?,
+                })
+            })
+            .collect()
+    }
+}
 
-    const INPUT: &str = "p=36,69 v=61,39
+impl Simulation for Solver {
+    type State = Vec<Robot>;
+
+    /// A state per second, until the robots are back where they started.
+    fn states<'a>(input: &'a Self::ParsedInput<'_>) -> impl Iterator<Item = Self::State> + 'a {
+        let step = |robots: &Vec<Robot>| {
+            let mut robots = robots.clone();
+            for robot in robots.iter_mut() {
+                robot.x = (robot.x + robot.vx).rem_euclid(WIDTH);
+                robot.y = (robot.y + robot.vy).rem_euclid(HEIGHT);
+            }
+            Some(robots)
+        };
+        std::iter::successors(Some(input.clone()), step).take((WIDTH * HEIGHT) as usize)
+    }
+
+    /// The number of robots on each tile, like the puzzle draws them.
+    fn draw(_: &Self::ParsedInput<'_>, robots: &Self::State) -> Picture {
+        let mut counts = Grid::new(HEIGHT as usize, WIDTH as usize, 0);
+        for robot in robots {
+            counts[(robot.y as usize, robot.x as usize)] += 1;
+        }
+        Picture::new(&counts, |n| match n {
+            0 => Tile::new('.', Color::GRAY),
+            n => Tile::new(char::from_digit(*n.min(&9), 10).unwrap(), Color::GREEN),
+        })
+    }
+}
+
+impl Example for Solver {
+    const EXAMPLE: &'static str = "p=36,69 v=61,39
 p=26,89 v=85,12
 p=45,27 v=25,-53
 p=99,30 v=-75,-86
@@ -613,6 +647,16 @@ p=44,90 v=6,-71
 p=17,44 v=-71,50
 p=1,51 v=69,79
 ";
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Solver;
+    use aoc_traits::AdventOfCodeDay;
+    use parsing::Example;
+    use render::Simulation;
+
+    const INPUT: &str = Solver::EXAMPLE;
 
     #[test]
     fn test_part1() {
@@ -625,4 +669,72 @@ p=1,51 v=69,79
         let parsed = Solver::parse_input(INPUT);
         assert_eq!(Solver::solve_part2(&parsed), 7138);
     }
+
+    #[test]
+    fn test_input_variants() {
+        for input in parsing::variants(INPUT) {
+            let parsed = Solver::parse_input(&input);
+            assert_eq!(Solver::solve_part1(&parsed), 229839456);
+        }
+    }
+
+    #[test]
+    fn test_states() {
+        let parsed = Solver::parse_input(INPUT);
+        assert_eq!(Solver::states(&parsed).count(), 101 * 103);
+        // the picture part 2 looks for, framed by a line of robots
+        let tree = Solver::states(&parsed).nth(7138).unwrap();
+        let picture = Solver::draw(&parsed, &tree).to_string();
+        assert!(picture.contains(&"1".repeat(31)));
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use crate::{Solver, HEIGHT, WIDTH};
+    use aoc_traits::AdventOfCodeDay;
+    use proptest::prelude::*;
+
+    type Robot = ((i64, i64), (i64, i64));
+
+    /// Moves the robots one second at a time.
+    fn reference_part1(robots: &[Robot]) -> usize {
+        let mut robots = robots.to_vec();
+        for _ in 0..100 {
+            for ((x, y), (vx, vy)) in &mut robots {
+                *x = (*x + *vx + WIDTH) % WIDTH;
+                *y = (*y + *vy + HEIGHT) % HEIGHT;
+            }
+        }
+        let quadrant = |xs: std::ops::Range<i64>, ys: std::ops::Range<i64>| {
+            robots
+                .iter()
+                .filter(|((x, y), _)| xs.contains(x) && ys.contains(y))
+                .count()
+        };
+        let (left, right) = (0..WIDTH / 2, WIDTH / 2 + 1..WIDTH);
+        let (top, bottom) = (0..HEIGHT / 2, HEIGHT / 2 + 1..HEIGHT);
+        quadrant(left.clone(), top.clone())
+            * quadrant(right.clone(), top)
+            * quadrant(left, bottom.clone())
+            * quadrant(right, bottom)
+    }
+
+    fn robots() -> impl Strategy<Value = Vec<Robot>> {
+        let robot = ((0..WIDTH, 0..HEIGHT), (-100..=100i64, -100..=100i64));
+        prop::collection::vec(robot, 1..50)
+    }
+
+    // Part 2 looks for a picture, which has no simpler definition to check against.
+    proptest! {
+        #[test]
+        fn part1_matches_reference(robots in robots()) {
+            let input = robots
+                .iter()
+                .map(|((x, y), (vx, vy))| format!("p={x},{y} v={vx},{vy}\n"))
+                .collect::<String>();
+            let parsed = Solver::parse_input(&input);
+            prop_assert_eq!(Solver::solve_part1(&parsed), reference_part1(&robots));
+        }
+    }
 }
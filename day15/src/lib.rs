@@ -1,6 +1,7 @@
-use std::fmt::Display;
-
 use aoc_traits::AdventOfCodeDay;
+use grid::{Direction, Grid, Position};
+use parsing::{Example, ParseError, Source, TryParse};
+use render::{Color, Picture, Render, Simulation, Tile};
 
 #[derive(Debug, Clone, Copy)]
 pub enum Cell {
@@ -11,162 +12,124 @@ pub enum Cell {
     Empty,
 }
 
-impl Display for Cell {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Cell::Wall => write!(f, "#"),
-            Cell::Box => write!(f, "O"),
-            Cell::Empty => write!(f, "."),
-            Cell::BoxL => write!(f, "["),
-            Cell::BoxR => write!(f, "]"),
-        }
+/// Moves the robot at `(row, col)` one step in direction `m`, pushing the boxes in front of it,
+/// and returns where it ends up.
+fn push(map: &mut Grid<Cell>, (mut row, mut col): Position, m: Direction) -> Position {
+    match m {
+        Direction::West => match map[(row, col - 1)] {
+            Cell::Wall => {}
+            Cell::Box => {
+                if let Some(pos) = map
+                    .row(row)
+                    .iter()
+                    .take(col)
+                    .rev()
+                    .take_while(|c| matches!(c, Cell::Empty | Cell::Box))
+                    .position(|c| matches!(c, Cell::Empty))
+                {
+                    map[(row, col - pos - 1)] = Cell::Box;
+                    map[(row, col - 1)] = Cell::Empty;
+                    col -= 1;
+                }
+            }
+            Cell::Empty => {
+                col -= 1;
+            }
+            _ => unreachable!(),
+        },
+        Direction::East => match map[(row, col + 1)] {
+            Cell::Wall => {}
+            Cell::Box => {
+                if let Some(pos) = map
+                    .row(row)
+                    .iter()
+                    .skip(col + 1)
+                    .take_while(|c| matches!(c, Cell::Empty | Cell::Box))
+                    .position(|c| matches!(c, Cell::Empty))
+                {
+                    map[(row, col + pos + 1)] = Cell::Box;
+                    map[(row, col + 1)] = Cell::Empty;
+                    col += 1;
+                }
+            }
+            Cell::Empty => {
+                col += 1;
+            }
+            _ => unreachable!(),
+        },
+        Direction::North => match map[(row - 1, col)] {
+            Cell::Wall => {}
+            Cell::Box => {
+                if let Some(pos) = map
+                    .column(col)
+                    .take(row)
+                    .rev()
+                    .take_while(|c| matches!(c, Cell::Empty | Cell::Box))
+                    .position(|c| matches!(c, Cell::Empty))
+                {
+                    map[(row - pos - 1, col)] = Cell::Box;
+                    map[(row - 1, col)] = Cell::Empty;
+                    row -= 1;
+                }
+            }
+            Cell::Empty => {
+                row -= 1;
+            }
+            _ => unreachable!(),
+        },
+        Direction::South => match map[(row + 1, col)] {
+            Cell::Wall => {}
+            Cell::Box => {
+                if let Some(pos) = map
+                    .column(col)
+                    .skip(row + 1)
+                    .take_while(|c| matches!(c, Cell::Empty | Cell::Box))
+                    .position(|c| matches!(c, Cell::Empty))
+                {
+                    map[(row + pos + 1, col)] = Cell::Box;
+                    map[(row + 1, col)] = Cell::Empty;
+                    row += 1;
+                }
+            }
+            Cell::Empty => {
+                row += 1;
+            }
+            _ => unreachable!(),
+        },
     }
+    (row, col)
 }
 
-#[derive(Debug, Clone, Copy)]
-pub enum Move {
-    Left,
-    Right,
-    Up,
-    Down,
+/// The warehouse and the robot's position after all of part 1's moves.
+fn warehouse(
+    (map, robot, moves): &(Grid<Cell>, Position, Vec<Direction>),
+) -> (Grid<Cell>, Position) {
+    let mut map = map.clone();
+    let mut robot = *robot;
+    for m in moves {
+        robot = push(&mut map, robot, *m);
+    }
+    (map, robot)
 }
 
 #[derive(Default)]
 pub struct Solver;
 impl AdventOfCodeDay for Solver {
-    type ParsedInput<'a> = (Vec<Vec<Cell>>, (usize, usize), Vec<Move>);
+    type ParsedInput<'a> = (Grid<Cell>, Position, Vec<Direction>);
     type Part1Output = usize;
     type Part2Output = usize;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        let (map, moves) = input.split_once("\n\n").unwrap();
-        let mut pos = None;
-        let map = map
-            .lines()
-            .enumerate()
-            .map(|(row, line)| {
-                line.chars()
-                    .enumerate()
-                    .map(|(col, c)| match c {
-                        '#' => Cell::Wall,
-                        'O' => Cell::Box,
-                        '.' => Cell::Empty,
-                        '@' => {
-                            pos = Some((row, col));
-                            Cell::Empty
-                        }
-                        _ => unreachable!(),
-                    })
-                    .collect()
-            })
-            .collect();
-        let moves = moves
-            .lines()
-            .flat_map(|line| {
-                line.chars().map(|c| match c {
-                    '>' => Move::Right,
-                    '<' => Move::Left,
-                    'v' => Move::Down,
-                    '^' => Move::Up,
-                    _ => unreachable!(),
-                })
-            })
-            .collect();
-        (map, pos.unwrap(), moves)
+        Self::try_parse_input(input).unwrap_or_else(|e| panic!("{e}"))
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
-        let mut map = input.0.clone();
-        let (mut row, mut col) = input.1;
-        let moves = &input.2;
-
-        for m in moves {
-            match m {
-                Move::Left => match map[row][col - 1] {
-                    Cell::Wall => {}
-                    Cell::Box => {
-                        if let Some(pos) = map[row]
-                            .iter()
-                            .take(col)
-                            .rev()
-                            .take_while(|c| matches!(c, Cell::Empty | Cell::Box))
-                            .position(|c| matches!(c, Cell::Empty))
-                        {
-                            map[row][col - pos - 1] = Cell::Box;
-                            map[row][col - 1] = Cell::Empty;
-                            col -= 1;
-                        }
-                    }
-                    Cell::Empty => {
-                        col -= 1;
-                    }
-                    _ => unreachable!(),
-                },
-                Move::Right => match map[row][col + 1] {
-                    Cell::Wall => {}
-                    Cell::Box => {
-                        if let Some(pos) = map[row]
-                            .iter()
-                            .skip(col + 1)
-                            .take_while(|c| matches!(c, Cell::Empty | Cell::Box))
-                            .position(|c| matches!(c, Cell::Empty))
-                        {
-                            map[row][col + pos + 1] = Cell::Box;
-                            map[row][col + 1] = Cell::Empty;
-                            col += 1;
-                        }
-                    }
-                    Cell::Empty => {
-                        col += 1;
-                    }
-                    _ => unreachable!(),
-                },
-                Move::Up => match map[row - 1][col] {
-                    Cell::Wall => {}
-                    Cell::Box => {
-                        if let Some(pos) = (0..row)
-                            .map(|r| map[r][col])
-                            .rev()
-                            .take_while(|c| matches!(c, Cell::Empty | Cell::Box))
-                            .position(|c| matches!(c, Cell::Empty))
-                        {
-                            map[row - pos - 1][col] = Cell::Box;
-                            map[row - 1][col] = Cell::Empty;
-                            row -= 1;
-                        }
-                    }
-                    Cell::Empty => {
-                        row -= 1;
-                    }
-                    _ => unreachable!(),
-                },
-                Move::Down => match map[row + 1][col] {
-                    Cell::Wall => {}
-                    Cell::Box => {
-                        if let Some(pos) = (row + 1..map.len())
-                            .map(|r| map[r][col])
-                            .take_while(|c| matches!(c, Cell::Empty | Cell::Box))
-                            .position(|c| matches!(c, Cell::Empty))
-                        {
-                            map[row + pos + 1][col] = Cell::Box;
-                            map[row + 1][col] = Cell::Empty;
-                            row += 1;
-                        }
-                    }
-                    Cell::Empty => {
-                        row += 1;
-                    }
-                    _ => unreachable!(),
-                },
-            }
-        }
+        let (map, _) = warehouse(input);
 
         let mut sum = 0;
-        for (r, row_) in map.iter().enumerate() {
-            for (c, cell) in row_.iter().enumerate() {
-                if let Cell::Box = cell {
-                    sum += 100 * r + c;
-                }
+        for ((r, c), cell) in map.iter() {
+            if let Cell::Box = cell {
+                sum += 100 * r + c;
             }
         }
         sum
@@ -176,49 +139,26 @@ impl AdventOfCodeDay for Solver {
         let (mut row, mut col) = input.1;
         let moves = &input.2;
 
-        let mut map = Vec::with_capacity(input.0.len());
-        for row in &input.0 {
-            let mut row_ = Vec::with_capacity(row.len() * 2);
-            for c in row {
-                match c {
-                    Cell::Wall => {
-                        row_.push(Cell::Wall);
-                        row_.push(Cell::Wall);
-                    }
-                    Cell::Box => {
-                        row_.push(Cell::BoxL);
-                        row_.push(Cell::BoxR);
-                    }
-                    Cell::Empty => {
-                        row_.push(Cell::Empty);
-                        row_.push(Cell::Empty);
-                    }
-                    _ => unreachable!(),
-                }
+        let map = &input.0;
+        let mut map = Grid::from_fn(map.rows(), map.cols() * 2, |(r, c)| {
+            match (map[(r, c / 2)], c % 2) {
+                (Cell::Wall, _) => Cell::Wall,
+                (Cell::Box, 0) => Cell::BoxL,
+                (Cell::Box, _) => Cell::BoxR,
+                (Cell::Empty, _) => Cell::Empty,
+                _ => unreachable!(),
             }
-            map.push(row_);
-        }
+        });
 
         col *= 2;
 
-        // for (r, row_) in map.iter().enumerate() {
-        //     for (c, cell) in row_.iter().enumerate() {
-        //         if r == row && c == col {
-        //             print!("@");
-        //         } else {
-        //             print!("{cell}");
-        //         }
-        //     }
-        //     println!();
-        // }
-
         for m in moves {
-            // println!("move = {m:?}");
             match m {
-                Move::Left => match map[row][col - 1] {
+                Direction::West => match map[(row, col - 1)] {
                     Cell::Wall => {}
                     Cell::BoxR => {
-                        if let Some(pos) = map[row]
+                        if let Some(pos) = map
+                            .row(row)
                             .iter()
                             .take(col)
                             .rev()
@@ -226,10 +166,10 @@ impl AdventOfCodeDay for Solver {
                             .position(|c| matches!(c, Cell::Empty))
                         {
                             for i in (col - pos - 1..col - 1).step_by(2) {
-                                map[row][i] = Cell::BoxL;
-                                map[row][i + 1] = Cell::BoxR;
+                                map[(row, i)] = Cell::BoxL;
+                                map[(row, i + 1)] = Cell::BoxR;
                             }
-                            map[row][col - 1] = Cell::Empty;
+                            map[(row, col - 1)] = Cell::Empty;
                             col -= 1;
                         }
                     }
@@ -238,20 +178,21 @@ impl AdventOfCodeDay for Solver {
                     }
                     _ => unreachable!(),
                 },
-                Move::Right => match map[row][col + 1] {
+                Direction::East => match map[(row, col + 1)] {
                     Cell::Wall => {}
                     Cell::BoxL => {
-                        if let Some(pos) = map[row]
+                        if let Some(pos) = map
+                            .row(row)
                             .iter()
                             .skip(col + 1)
                             .take_while(|c| matches!(c, Cell::Empty | Cell::BoxL | Cell::BoxR))
                             .position(|c| matches!(c, Cell::Empty))
                         {
                             for i in (col + 2..col + pos + 1).step_by(2) {
-                                map[row][i] = Cell::BoxL;
-                                map[row][i + 1] = Cell::BoxR;
+                                map[(row, i)] = Cell::BoxL;
+                                map[(row, i + 1)] = Cell::BoxR;
                             }
-                            map[row][col + 1] = Cell::Empty;
+                            map[(row, col + 1)] = Cell::Empty;
                             col += 1;
                         }
                     }
@@ -260,11 +201,12 @@ impl AdventOfCodeDay for Solver {
                     }
                     _ => unreachable!(),
                 },
-                Move::Up => match map[row - 1][col] {
+                Direction::North => match map[(row - 1, col)] {
                     Cell::Wall => {}
                     Cell::BoxL => {
-                        if let Some(pos) = (0..row)
-                            .map(|r| map[r][col])
+                        if let Some(pos) = map
+                            .column(col)
+                            .take(row)
                             .rev()
                             .take_while(|c| matches!(c, Cell::Empty | Cell::BoxL | Cell::BoxR))
                             .position(|c| matches!(c, Cell::Empty))
@@ -273,8 +215,9 @@ impl AdventOfCodeDay for Solver {
                         }
                     }
                     Cell::BoxR => {
-                        if let Some(pos) = (0..row)
-                            .map(|r| map[r][col])
+                        if let Some(pos) = map
+                            .column(col)
+                            .take(row)
                             .rev()
                             .take_while(|c| matches!(c, Cell::Empty | Cell::BoxL | Cell::BoxR))
                             .position(|c| matches!(c, Cell::Empty))
@@ -287,11 +230,12 @@ impl AdventOfCodeDay for Solver {
                     }
                     _ => unreachable!(),
                 },
-                Move::Down => match map[row + 1][col] {
+                Direction::South => match map[(row + 1, col)] {
                     Cell::Wall => {}
                     Cell::BoxL => {
-                        if let Some(pos) = (row + 1..map.len())
-                            .map(|r| map[r][col])
+                        if let Some(pos) = map
+                            .column(col)
+                            .skip(row + 1)
                             .take_while(|c| matches!(c, Cell::Empty | Cell::Box))
                             .position(|c| matches!(c, Cell::Empty))
                         {
@@ -299,8 +243,9 @@ impl AdventOfCodeDay for Solver {
                         }
                     }
                     Cell::BoxR => {
-                        if let Some(pos) = (row + 1..map.len())
-                            .map(|r| map[r][col])
+                        if let Some(pos) = map
+                            .column(col)
+                            .skip(row + 1)
                             .take_while(|c| matches!(c, Cell::Empty | Cell::Box))
                             .position(|c| matches!(c, Cell::Empty))
                         {
@@ -313,36 +258,102 @@ impl AdventOfCodeDay for Solver {
                     _ => unreachable!(),
                 },
             }
-            // for (r, row_) in map.iter().enumerate() {
-            //     for (c, cell) in row_.iter().enumerate() {
-            //         if r == row && c == col {
-            //             print!("@");
-            //         } else {
-            //             print!("{cell}");
-            //         }
-            //     }
-            //     println!();
-            // }
         }
 
         let mut sum = 0;
-        for (r, row_) in map.iter().enumerate() {
-            for (c, cell) in row_.iter().enumerate() {
-                if let Cell::Box = cell {
-                    sum += 100 * r + c;
-                }
+        for ((r, c), cell) in map.iter() {
+            if let Cell::Box = cell {
+                sum += 100 * r + c;
             }
         }
         sum
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::Solver;
-    use aoc_traits::AdventOfCodeDay;
+impl TryParse for Solver {
+    fn try_parse_input(input: &str) -> Result<Self::ParsedInput<'_>, ParseError> {
+        let src = Source::new(input);
+        let (text, moves) = src.two_sections()?;
+        let mut pos = None;
+        let map = Grid::parse(&src, text, |p, c| match c {
+            '#' => Ok(Cell::Wall),
+            'O' => Ok(Cell::Box),
+            '.' => Ok(Cell::Empty),
+            '@' => {
+                pos = Some(p);
+                Ok(Cell::Empty)
+            }
+            _ => Err("expected one of `#`, `O`, `.` or `@`"),
+        })?;
+        // `push` relies on the wall to stop the robot before it leaves the map
+        let edge = |(row, col): Position| {
+            row == 0 || col == 0 || row == map.rows() - 1 || col == map.cols() - 1
+        };
+        if let Some((row, col)) = map
+            .positions()
+            .find(|pos| edge(*pos) && !matches!(map[*pos], Cell::Wall))
+        {
+            let line = parsing::lines(text).nth(row).unwrap_or_default();
+            return Err(src.error_at(line, col, "expected a wall `#` around the map"));
+        }
+        let moves = parsing::lines(moves)
+            .flat_map(|line| {
+                line.char_indices().map(|(i, c)| {
+                    Direction::from_arrow(c).ok_or_else(|| {
+                        src.error_at(line, i, "expected one of `<`, `>`, `^` or `v`")
+                    })
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        let Some(pos) = pos else {
+            return Err(src.error(input, "no robot (`@`) on the map"));
+        };
+        Ok((map, pos, moves))
+    }
+}
+
+/// The warehouse, with the robot drawn as `@`.
+fn picture(map: &Grid<Cell>, robot: Position) -> Picture {
+    let mut picture = Picture::new(map, |cell| match cell {
+        Cell::Wall => Tile::new('#', Color::GRAY),
+        Cell::Box => Tile::new('O', Color::YELLOW),
+        Cell::BoxL => Tile::new('[', Color::YELLOW),
+        Cell::BoxR => Tile::new(']', Color::YELLOW),
+        Cell::Empty => Tile::new('.', Color::BLACK),
+    });
+    picture.draw(robot, '@', Color::RED);
+    picture
+}
+
+/// The warehouse after part 1's moves.
+impl Render for Solver {
+    fn render(input: &Self::ParsedInput<'_>) -> Picture {
+        let (map, robot) = warehouse(input);
+        picture(&map, robot)
+    }
+}
+
+impl Simulation for Solver {
+    type State = (Grid<Cell>, Position);
 
-    const INPUT: &str = "##########
+    /// The warehouse before part 1's moves and after each of them.
+    fn states<'a>(input: &'a Self::ParsedInput<'_>) -> impl Iterator<Item = Self::State> + 'a {
+        let (map, robot, moves) = input;
+        let mut state = (map.clone(), *robot);
+        let start = state.clone();
+        std::iter::once(start).chain(moves.iter().map(move |m| {
+            state.1 = push(&mut state.0, state.1, *m);
+            state.clone()
+        }))
+    }
+
+    fn draw(_: &Self::ParsedInput<'_>, (map, robot): &Self::State) -> Picture {
+        picture(map, *robot)
+    }
+}
+
+impl Example for Solver {
+    const EXAMPLE: &'static str = "##########
 #..O..O.O#
 #......O.#
 #.OO..O.O#
@@ -364,6 +375,16 @@ vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
 ^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
 v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^
 ";
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Solver;
+    use aoc_traits::AdventOfCodeDay;
+    use parsing::{Example, TryParse};
+    use render::{Render, Simulation};
+
+    const INPUT: &str = Solver::EXAMPLE;
 
     #[test]
     fn test_part1() {
@@ -376,4 +397,143 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^
         let parsed = Solver::parse_input(INPUT);
         assert_eq!(Solver::solve_part2(&parsed), 9021);
     }
+
+    #[test]
+    fn test_render() {
+        let picture = Solver::render(&Solver::parse_input(INPUT));
+        assert_eq!(
+            picture.to_string(),
+            "##########
+#.O.O.OOO#
+#........#
+#OO......#
+#OO@.....#
+#O#.....O#
+#O.....OO#
+#O.....OO#
+#OO....OO#
+##########
+"
+        );
+    }
+
+    #[test]
+    fn test_states() {
+        let parsed = Solver::parse_input(INPUT);
+        let states = Solver::states(&parsed).collect::<Vec<_>>();
+        assert_eq!(states.len(), parsed.2.len() + 1);
+        assert_eq!(states[0].1, parsed.1);
+        let last = Solver::draw(&parsed, states.last().unwrap());
+        assert_eq!(last, Solver::render(&parsed));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let e = Solver::try_parse_input("#####\n#.@x#\n#####\n\n<>\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 4, "x"));
+        let e = Solver::try_parse_input("#####\n@..O#\n#####\n\n<>\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 1, "@"));
+    }
+
+    #[test]
+    fn test_input_variants() {
+        for input in parsing::variants(INPUT) {
+            let parsed = Solver::parse_input(&input);
+            assert_eq!(Solver::solve_part1(&parsed), 10092);
+        }
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use crate::Solver;
+    use aoc_traits::AdventOfCodeDay;
+    use proptest::prelude::*;
+
+    type Warehouse = (Vec<Vec<u8>>, (usize, usize), Vec<u8>);
+
+    fn render((map, robot, moves): &Warehouse) -> String {
+        let mut out = String::new();
+        for (r, row) in map.iter().enumerate() {
+            for (c, cell) in row.iter().enumerate() {
+                out.push(if (r, c) == *robot { '@' } else { *cell as char });
+            }
+            out.push('\n');
+        }
+        out.push('\n');
+        for line in moves.chunks(20) {
+            out.push_str(&format!("{}\n", String::from_utf8_lossy(line)));
+        }
+        out
+    }
+
+    /// Walks over the boxes in front of the robot and, if there is space behind them, moves the
+    /// first one there.
+    fn reference_part1((map, robot, moves): &Warehouse) -> usize {
+        let mut map = map.clone();
+        let (mut r, mut c) = *robot;
+        for m in moves {
+            let (dr, dc) = match m {
+                b'^' => (-1, 0),
+                b'v' => (1, 0),
+                b'<' => (0, -1),
+                _ => (0, 1),
+            };
+            let step =
+                |(r, c): (usize, usize)| (r.wrapping_add_signed(dr), c.wrapping_add_signed(dc));
+            let mut end = step((r, c));
+            while map[end.0][end.1] == b'O' {
+                end = step(end);
+            }
+            if map[end.0][end.1] == b'.' {
+                (r, c) = step((r, c));
+                map[end.0][end.1] = map[r][c];
+                map[r][c] = b'.';
+            }
+        }
+        let mut sum = 0;
+        for (r, row) in map.iter().enumerate() {
+            for (c, cell) in row.iter().enumerate() {
+                if *cell == b'O' {
+                    sum += 100 * r + c;
+                }
+            }
+        }
+        sum
+    }
+
+    /// Walled in maps with the robot somewhere inside.
+    fn warehouse() -> impl Strategy<Value = Warehouse> {
+        (3..12usize, 3..12usize)
+            .prop_flat_map(|(rows, cols)| {
+                let cell = prop::sample::select(&b"...O#"[..]);
+                let moves = prop::collection::vec(prop::sample::select(&b"<>^v"[..]), 1..100);
+                (
+                    prop::collection::vec(prop::collection::vec(cell, cols), rows),
+                    (1..rows - 1, 1..cols - 1),
+                    moves,
+                )
+            })
+            .prop_map(|(mut map, robot, moves)| {
+                let (rows, cols) = (map.len(), map[0].len());
+                for (r, row) in map.iter_mut().enumerate() {
+                    for (c, cell) in row.iter_mut().enumerate() {
+                        if r == 0 || c == 0 || r == rows - 1 || c == cols - 1 {
+                            *cell = b'#';
+                        }
+                    }
+                }
+                map[robot.0][robot.1] = b'.';
+                (map, robot, moves)
+            })
+    }
+
+    // Part 2 is not done yet.
+    proptest! {
+        #[test]
+        fn part1_matches_reference(warehouse in warehouse()) {
+            let parsed = Solver::parse_input(&render(&warehouse));
+            prop_assert_eq!(Solver::solve_part1(&parsed), reference_part1(&warehouse));
+        }
+    }
 }
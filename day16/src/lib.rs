@@ -1,268 +1,118 @@
-use std::{
-    cmp::Ordering,
-    collections::{BinaryHeap, HashMap, HashSet},
-};
+use std::convert::Infallible;
 
 use aoc_traits::AdventOfCodeDay;
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Dir {
-    North,
-    East,
-    South,
-    West,
-}
-
-impl Dir {
-    fn next(&self) -> [Self; 3] {
-        match self {
-            Dir::North => [Dir::West, Dir::North, Dir::East],
-            Dir::East => [Dir::North, Dir::East, Dir::South],
-            Dir::South => [Dir::East, Dir::South, Dir::West],
-            Dir::West => [Dir::South, Dir::West, Dir::North],
-        }
-    }
-
-    pub fn offset(&self, row: usize, col: usize) -> (usize, usize) {
-        match self {
-            Dir::West => (row, col - 1),
-            Dir::East => (row, col + 1),
-            Dir::North => (row - 1, col),
-            Dir::South => (row + 1, col),
-        }
-    }
-}
-
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-struct State {
-    cost: usize,
-    position: Position,
-    direction: Dir,
-}
-
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other
-            .cost
-            .cmp(&self.cost)
-            .then_with(|| self.position.cmp(&other.position))
+use grid::{Direction, Grid, Position};
+use parsing::{Example, ParseError, Source, TryParse};
+use pathfinding::{Graph, ShortestPaths};
+use render::{Color, Picture, Render, Tile};
+
+type Node = (Position, Direction);
+
+/// Stepping ahead costs 1 and turning on the spot costs 1000.
+fn moves(map: &Grid<u8>) -> impl Graph<Node> + '_ {
+    |(pos, dir): Node| {
+        let ahead = map
+            .offset(pos, dir.step())
+            .filter(|next| map[*next] != b'#')
+            .map(|next| ((next, dir), 1));
+        [
+            ((pos, dir.turn_left()), 1000),
+            ((pos, dir.turn_right()), 1000),
+        ]
+        .into_iter()
+        .chain(ahead)
     }
 }
 
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-type Position = (usize, usize);
-
-fn walk(
-    predecessors: &HashMap<(Position, Dir), Vec<(Position, Dir)>>,
-    pos: (Position, Dir),
-    path: &mut HashSet<(Position, Dir)>,
-) {
-    let preds = predecessors.get(&pos).unwrap();
-    path.insert(pos);
-    for pred in preds {
-        if !path.contains(pred) {
-            walk(predecessors, *pred, path);
-        }
-    }
+/// The tiles on any of the best paths.
+fn best_tiles((map, start, end): &(Grid<u8>, Position, Position)) -> Vec<Position> {
+    let paths = ShortestPaths::new(&moves(map), (*start, Direction::East));
+
+    // only the directions the end is reached in with the best score
+    let ends = Direction::ALL.map(|dir| (*end, dir));
+    let Some(best) = ends.iter().filter_map(|end| paths.distance(end)).min() else {
+        return vec![];
+    };
+    let ends = ends
+        .into_iter()
+        .filter(|end| paths.distance(end) == Some(best));
+
+    let mut tiles = paths
+        .nodes_on_paths(ends)
+        .into_iter()
+        .map(|(pos, _)| pos)
+        .collect::<Vec<_>>();
+    tiles.sort_unstable();
+    tiles.dedup();
+    tiles
 }
 
 #[derive(Default)]
 pub struct Solver;
 impl AdventOfCodeDay for Solver {
-    type ParsedInput<'a> = (Vec<Vec<u8>>, Position, Position);
+    type ParsedInput<'a> = (Grid<u8>, Position, Position);
     type Part1Output = usize;
     type Part2Output = usize;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        let mut start = None;
-        let mut end = None;
-        let map = input
-            .lines()
-            .enumerate()
-            .map(|(r, row)| {
-                row.as_bytes()
-                    .iter()
-                    .enumerate()
-                    .map(|(c, e)| {
-                        if *e == b'S' {
-                            start = Some((r, c));
-                            b'.'
-                        } else if *e == b'E' {
-                            end = Some((r, c));
-                            b'.'
-                        } else {
-                            *e
-                        }
-                    })
-                    .collect()
-            })
-            .collect();
-        (map, start.unwrap(), end.unwrap())
+        Self::try_parse_input(input).unwrap_or_else(|e| panic!("{e}"))
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
-        let map = &input.0;
-        let start = input.1;
-        let end = input.2;
-
-        let rows = map.len();
-        let cols = map[0].len();
-
-        let mut dist = HashMap::new();
-        for row in 0..rows {
-            for col in 0..cols {
-                for dir in [Dir::North, Dir::East, Dir::South, Dir::West] {
-                    dist.insert(((row, col), dir), usize::MAX);
-                }
-            }
-        }
-
-        dist.insert((start, Dir::East), 0);
-
-        let mut heap = BinaryHeap::new();
-        heap.push(State {
-            cost: 0,
-            position: start,
-            direction: Dir::East,
-        });
-
-        while let Some(State {
-            cost,
-            position,
-            direction,
-        }) = heap.pop()
-        {
-            if position == end {
-                return cost;
-            }
-
-            if cost > *dist.get(&(position, direction)).unwrap() {
-                continue;
-            }
-
-            for dir in direction.next() {
-                let (row, col, cost_) = if dir == direction {
-                    let (row, col) = dir.offset(position.0, position.1);
-                    if map[row][col] != b'#' {
-                        (row, col, 1)
-                    } else {
-                        continue;
-                    }
-                } else {
-                    (position.0, position.1, 1000)
-                };
-
-                let next = State {
-                    cost: cost.saturating_add(cost_),
-                    position: (row, col),
-                    direction: dir,
-                };
-
-                let cur_cost = *dist.get(&(next.position, dir)).unwrap();
-                if next.cost < cur_cost {
-                    heap.push(next);
-                    dist.insert((next.position, dir), next.cost);
-                }
-            }
-        }
-        0
+        let (map, start, end) = input;
+        pathfinding::dijkstra(&moves(map), (*start, Direction::East), |(pos, _)| {
+            pos == end
+        })
+        .map_or(0, |path| path.cost)
     }
 
     fn solve_part2(input: &Self::ParsedInput<'_>) -> Self::Part2Output {
-        let map = &input.0;
-        let start = input.1;
-        let end = input.2;
-
-        let rows = map.len();
-        let cols = map[0].len();
+        best_tiles(input).len()
+    }
+}
 
-        let mut dist = HashMap::new();
-        let mut predecessors = HashMap::new();
-        for row in 0..rows {
-            for col in 0..cols {
-                for dir in [Dir::North, Dir::East, Dir::South, Dir::West] {
-                    dist.insert(((row, col), dir), usize::MAX);
-                    predecessors.insert(((row, col), dir), Vec::new());
+impl TryParse for Solver {
+    fn try_parse_input(input: &str) -> Result<Self::ParsedInput<'_>, ParseError> {
+        let mut start = None;
+        let mut end = None;
+        let src = Source::new(input);
+        let map = Grid::parse(&src, input, |pos, c| {
+            Ok::<_, Infallible>(match c {
+                'S' => {
+                    start = Some(pos);
+                    b'.'
                 }
-            }
-        }
-
-        dist.insert((start, Dir::East), 0);
-
-        let mut heap = BinaryHeap::new();
-        heap.push(State {
-            cost: 0,
-            position: start,
-            direction: Dir::East,
-        });
-
-        while let Some(State {
-            cost,
-            position,
-            direction,
-        }) = heap.pop()
-        {
-            if cost > *dist.get(&(position, direction)).unwrap() {
-                continue;
-            }
-
-            for dir in direction.next() {
-                let (row, col, cost_) = if dir == direction {
-                    let (row, col) = dir.offset(position.0, position.1);
-                    if map[row][col] != b'#' {
-                        (row, col, 1)
-                    } else {
-                        continue;
-                    }
-                } else {
-                    (position.0, position.1, 1000)
-                };
-
-                let next = State {
-                    cost: cost.saturating_add(cost_),
-                    position: (row, col),
-                    direction: dir,
-                };
-
-                let cur_cost = *dist.get(&(next.position, dir)).unwrap();
-                if next.cost < cur_cost {
-                    heap.push(next);
-                    dist.insert((next.position, dir), next.cost);
-                    predecessors.insert((next.position, dir), vec![(position, direction)]);
-                } else if next.cost == cur_cost {
-                    predecessors
-                        .entry((next.position, dir))
-                        .or_default()
-                        .push((position, direction));
+                'E' => {
+                    end = Some(pos);
+                    b'.'
                 }
-            }
-        }
-
-        let mut path = HashSet::new();
-        for dir in [Dir::North, Dir::East, Dir::South, Dir::West] {
-            walk(&predecessors, (end, dir), &mut path);
-        }
-
-        let mut path_ = HashSet::new();
-        for (pos, _) in path.iter() {
-            path_.insert(pos);
-        }
-
-        path_.len()
+                c => c as u8,
+            })
+        })?;
+        let start = start.ok_or_else(|| src.error(input, "no start tile (`S`) on the map"))?;
+        let end = end.ok_or_else(|| src.error(input, "no end tile (`E`) on the map"))?;
+        Ok((map, start, end))
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::Solver;
-    use aoc_traits::AdventOfCodeDay;
+/// The tiles on the best paths.
+impl Render for Solver {
+    fn render(input: &Self::ParsedInput<'_>) -> Picture {
+        let (map, start, end) = input;
+        let mut picture = Picture::new(map, |tile| match tile {
+            b'#' => Tile::new('#', Color::GRAY),
+            _ => Tile::new('.', Color::BLACK),
+        });
+        picture
+            .overlay(best_tiles(input), Color::GREEN)
+            .draw(*start, 'S', Color::RED)
+            .draw(*end, 'E', Color::RED);
+        picture
+    }
+}
 
-    const INPUT: &str = "###############
+impl Example for Solver {
+    const EXAMPLE: &'static str = "###############
 #.......#....E#
 #.#.###.#.###.#
 #.....#.#...#.#
@@ -278,6 +128,16 @@ mod tests {
 #S..#.....#...#
 ###############
 ";
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Solver;
+    use aoc_traits::AdventOfCodeDay;
+    use parsing::Example;
+    use render::Render;
+
+    const INPUT: &str = Solver::EXAMPLE;
 
     #[test]
     fn test_part1() {
@@ -290,4 +150,149 @@ mod tests {
         let parsed = Solver::parse_input(INPUT);
         assert_eq!(Solver::solve_part2(&parsed), 45);
     }
+
+    #[test]
+    fn test_render() {
+        let picture = Solver::render(&Solver::parse_input(INPUT));
+        assert_eq!(picture.to_string(), INPUT);
+        let tiles = picture.tiles().iter();
+        assert_eq!(tiles.filter(|(_, t)| t.background.is_some()).count(), 45);
+    }
+
+    #[test]
+    fn test_input_variants() {
+        for input in parsing::variants(INPUT) {
+            let parsed = Solver::parse_input(&input);
+            assert_eq!(Solver::solve_part1(&parsed), 7036);
+        }
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use crate::Solver;
+    use aoc_traits::AdventOfCodeDay;
+    use proptest::prelude::*;
+
+    /// East first, so that turning right is the next one.
+    const DIRECTIONS: [(isize, isize); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+
+    type Maze = (Vec<Vec<u8>>, (usize, usize), (usize, usize));
+    type State = (usize, usize, usize);
+    /// Indexed by row, column and direction.
+    type Scores = Vec<Vec<Vec<usize>>>;
+
+    fn render((map, start, end): &Maze) -> String {
+        let mut map = map.clone();
+        map[start.0][start.1] = b'S';
+        map[end.0][end.1] = b'E';
+        map.iter()
+            .map(|row| format!("{}\n", String::from_utf8_lossy(row)))
+            .collect()
+    }
+
+    /// Every move from `(r, c)` facing `d`, with its cost.
+    fn moves(map: &[Vec<u8>], (r, c, d): State) -> Vec<(State, usize)> {
+        let mut moves = vec![((r, c, (d + 1) % 4), 1000), ((r, c, (d + 3) % 4), 1000)];
+        let (dr, dc) = DIRECTIONS[d];
+        let (s, t) = (r.wrapping_add_signed(dr), c.wrapping_add_signed(dc));
+        if map[s][t] != b'#' {
+            moves.push(((s, t, d), 1));
+        }
+        moves
+    }
+
+    fn states(map: &[Vec<u8>]) -> Vec<State> {
+        let (rows, cols) = (map.len(), map[0].len());
+        (0..rows)
+            .flat_map(|r| (0..cols).flat_map(move |c| (0..4).map(move |d| (r, c, d))))
+            .filter(|(r, c, _)| map[*r][*c] != b'#')
+            .collect()
+    }
+
+    /// The lowest scores from the start to every state, and from every state to the end, by
+    /// relaxing every move until nothing changes.
+    fn scores((map, start, end): &Maze) -> (Scores, Scores) {
+        let mut from_start = vec![vec![vec![usize::MAX; 4]; map[0].len()]; map.len()];
+        let mut to_end = from_start.clone();
+        from_start[start.0][start.1][0] = 0;
+        to_end[end.0][end.1] = vec![0; 4];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for state @ (r, c, d) in states(map) {
+                for ((s, t, e), cost) in moves(map, state) {
+                    let score = from_start[r][c][d].saturating_add(cost);
+                    if score < from_start[s][t][e] {
+                        from_start[s][t][e] = score;
+                        changed = true;
+                    }
+                    let score = to_end[s][t][e].saturating_add(cost);
+                    if score < to_end[r][c][d] {
+                        to_end[r][c][d] = score;
+                        changed = true;
+                    }
+                }
+            }
+        }
+        (from_start, to_end)
+    }
+
+    fn reference_part1(maze: &Maze) -> usize {
+        let (from_start, _) = scores(maze);
+        let end = maze.2;
+        *from_start[end.0][end.1].iter().min().unwrap()
+    }
+
+    /// The tiles with a state whose scores to and from it add up to the best score.
+    fn reference_part2(maze: &Maze) -> usize {
+        let (from_start, to_end) = scores(maze);
+        let best = reference_part1(maze);
+        let mut tiles = 0;
+        for (r, row) in from_start.iter().enumerate() {
+            for (c, scores) in row.iter().enumerate() {
+                if (0..4).any(|d| scores[d].saturating_add(to_end[r][c][d]) == best) {
+                    tiles += 1;
+                }
+            }
+        }
+        tiles
+    }
+
+    /// Walled in mazes where the end can be reached, as in the puzzle.
+    fn maze() -> impl Strategy<Value = Maze> {
+        (4..12usize, 4..12usize)
+            .prop_flat_map(|(rows, cols)| {
+                let cell = prop::sample::select(&b"...#"[..]);
+                let inside = (1..rows - 1, 1..cols - 1);
+                (
+                    prop::collection::vec(prop::collection::vec(cell, cols), rows),
+                    inside.clone(),
+                    inside,
+                )
+            })
+            .prop_filter_map("the end has to be reachable", |(mut map, start, end)| {
+                let (rows, cols) = (map.len(), map[0].len());
+                for (r, row) in map.iter_mut().enumerate() {
+                    for (c, cell) in row.iter_mut().enumerate() {
+                        if r == 0 || c == 0 || r == rows - 1 || c == cols - 1 {
+                            *cell = b'#';
+                        }
+                    }
+                }
+                map[start.0][start.1] = b'.';
+                map[end.0][end.1] = b'.';
+                let maze = (map, start, end);
+                (start != end && reference_part1(&maze) != usize::MAX).then_some(maze)
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn matches_reference(maze in maze()) {
+            let parsed = Solver::parse_input(&render(&maze));
+            prop_assert_eq!(Solver::solve_part1(&parsed), reference_part1(&maze));
+            prop_assert_eq!(Solver::solve_part2(&parsed), reference_part2(&maze));
+        }
+    }
 }
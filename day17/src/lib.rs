@@ -1,4 +1,5 @@
 use aoc_traits::AdventOfCodeDay;
+use parsing::{Example, ParseError, Source, TryParse};
 
 const A: usize = 0;
 const B: usize = 1;
@@ -16,18 +17,20 @@ pub enum Instruction {
     Cdv,
 }
 
-impl From<u8> for Instruction {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for Instruction {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0 => Self::Adv,
-            1 => Self::Bxl,
-            2 => Self::Bst,
-            3 => Self::Jnz,
-            4 => Self::Bxc,
-            5 => Self::Out,
-            6 => Self::Bdv,
-            7 => Self::Cdv,
-            _ => unreachable!(),
+            0 => Ok(Self::Adv),
+            1 => Ok(Self::Bxl),
+            2 => Ok(Self::Bst),
+            3 => Ok(Self::Jnz),
+            4 => Ok(Self::Bxc),
+            5 => Ok(Self::Out),
+            6 => Ok(Self::Bdv),
+            7 => Ok(Self::Cdv),
+            _ => Err(value),
         }
     }
 }
@@ -53,7 +56,8 @@ impl Computer {
     pub fn run(&mut self) -> String {
         let mut output = String::new();
         loop {
-            let instr = Instruction::from(self.program[self.ip]);
+            let instr = Instruction::try_from(self.program[self.ip])
+                .expect("opcodes are checked by try_parse_input");
             let operand = self.program[self.ip + 1];
 
             match instr {
@@ -117,21 +121,7 @@ impl AdventOfCodeDay for Solver {
     type Part2Output = u32;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        let (regs, program) = input.split_once("\n\n").unwrap();
-        let regs = regs
-            .lines()
-            .map(|line| line["Register X: ".len()..].parse().unwrap())
-            .collect::<Vec<_>>();
-        let program = program["Program: ".len()..]
-            .trim()
-            .split(',')
-            .map(|e| e.parse().unwrap())
-            .collect();
-        Computer {
-            registers: [regs[0], regs[1], regs[2]],
-            ip: 0,
-            program,
-        }
+        Self::try_parse_input(input).unwrap_or_else(|e| panic!("{e}"))
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
@@ -144,17 +134,51 @@ impl AdventOfCodeDay for Solver {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::Solver;
-    use aoc_traits::AdventOfCodeDay;
+impl TryParse for Solver {
+    fn try_parse_input(input: &str) -> Result<Self::ParsedInput<'_>, ParseError> {
+        let src = Source::new(input);
+        let (regs, program) = src.two_sections()?;
+        let regs = parsing::lines(regs)
+            .map(|line| src.number(src.split_once(line, ": ")?.1))
+            .collect::<Result<Vec<_>, _>>()?;
+        let [a, b, c] = regs[..] else {
+            return Err(src.error(input, "expected registers A, B and C"));
+        };
+        let program = src.strip_prefix(program, "Program: ")?;
+        let values = program
+            .split(',')
+            .map(|e| match src.number(e)? {
+                value @ 0..=7 => Ok(value),
+                _ => Err(src.error(e, "expected a 3-bit number")),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if values.len() % 2 != 0 {
+            return Err(src.error(program, "expected pairs of opcode and operand"));
+        }
+        Ok(Computer {
+            registers: [a, b, c],
+            ip: 0,
+            program: values,
+        })
+    }
+}
 
-    const INPUT: &str = "Register A: 729
+impl Example for Solver {
+    const EXAMPLE: &'static str = "Register A: 729
 Register B: 0
 Register C: 0
 
 Program: 0,1,5,4,3,0
 ";
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Solver;
+    use aoc_traits::AdventOfCodeDay;
+    use parsing::{Example, TryParse};
+
+    const INPUT: &str = Solver::EXAMPLE;
 
     #[test]
     fn test_part1() {
@@ -170,4 +194,112 @@ Program: 0,1,5,4,3,0
         let parsed = Solver::parse_input(INPUT);
         assert_eq!(Solver::solve_part2(&parsed), 31);
     }
+
+    #[test]
+    fn test_parse_error() {
+        let e = Solver::try_parse_input(
+            "Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,8,4\n",
+        )
+        .unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (5, 14, "8"));
+    }
+
+    #[test]
+    fn test_input_variants() {
+        for input in parsing::variants(INPUT) {
+            let parsed = Solver::parse_input(&input);
+            assert_eq!(
+                Solver::solve_part1(&parsed),
+                "4,6,3,5,6,3,5,2,1,0".to_owned()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use crate::Solver;
+    use aoc_traits::AdventOfCodeDay;
+    use proptest::prelude::*;
+
+    type Program = ([u64; 3], Vec<u8>);
+
+    fn render((registers, program): &Program) -> String {
+        let program = program.iter().map(u8::to_string).collect::<Vec<_>>();
+        format!(
+            "Register A: {}\nRegister B: {}\nRegister C: {}\n\nProgram: {}\n",
+            registers[0],
+            registers[1],
+            registers[2],
+            program.join(",")
+        )
+    }
+
+    /// Runs the program on the numbers, or gives up on shifts that do not fit in 64 bits.
+    fn reference_part1((registers, program): &Program) -> Option<String> {
+        let [mut a, mut b, mut c] = *registers;
+        let mut ip = 0;
+        let mut output = vec![];
+        while ip < program.len() {
+            let (opcode, literal) = (program[ip], program[ip + 1] as u64);
+            // 7 only appears as a literal operand
+            let combo = [0, 1, 2, 3, a, b, c, 7][literal as usize];
+            let shifted = || (combo < 64).then(|| a >> combo);
+            match opcode {
+                0 => a = shifted()?,
+                1 => b ^= literal,
+                2 => b = combo % 8,
+                3 if a != 0 => {
+                    ip = literal as usize;
+                    continue;
+                }
+                3 => {}
+                4 => b ^= c,
+                5 => output.push((combo % 8).to_string()),
+                6 => b = shifted()?,
+                _ => c = shifted()?,
+            }
+            ip += 2;
+        }
+        Some(output.join(","))
+    }
+
+    /// Like the puzzle's programs, a loop that ends with dividing A by 8, printing and jumping
+    /// back to the start. Combo operands are never 7.
+    fn program() -> impl Strategy<Value = Program> {
+        let instruction = prop_oneof![
+            (Just(0u8), 0..7u8),
+            (Just(1u8), 0..8u8),
+            (Just(2u8), 0..7u8),
+            (Just(4u8), 0..8u8),
+            (Just(5u8), 0..7u8),
+            (Just(6u8), 0..7u8),
+            (Just(7u8), 0..7u8),
+        ];
+        (
+            (0..1u64 << 48, 0..8u64, 0..8u64),
+            prop::collection::vec(instruction, 0..6),
+            0..7u8,
+        )
+            .prop_map(|((a, b, c), body, out)| {
+                let mut program = body
+                    .into_iter()
+                    .flat_map(|(i, o)| [i, o])
+                    .collect::<Vec<_>>();
+                program.extend([0, 3, 5, out, 3, 0]);
+                ([a, b, c], program)
+            })
+            .prop_filter("shifts have to fit in 64 bits", |program| {
+                reference_part1(program).is_some()
+            })
+    }
+
+    // Part 2 is not done yet.
+    proptest! {
+        #[test]
+        fn part1_matches_reference(program in program()) {
+            let parsed = Solver::parse_input(&render(&program));
+            prop_assert_eq!(Some(Solver::solve_part1(&parsed)), reference_part1(&program));
+        }
+    }
 }
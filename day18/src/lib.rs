@@ -1,126 +1,34 @@
-use std::{
-    cmp::Ordering,
-    collections::{BinaryHeap, HashMap},
-};
-
 use aoc_traits::AdventOfCodeDay;
+use grid::{Direction, Grid, Position};
+use parsing::{Example, ParseError, Source, TryParse};
+use pathfinding::Path;
+use render::{Color, Picture, Render, Tile};
 
 const ROWS: usize = 71;
 const COLS: usize = 71;
 const BYTES: usize = 1024;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Dir {
-    North,
-    East,
-    South,
-    West,
-}
-
-impl Dir {
-    pub fn offset(&self, row: usize, col: usize) -> Option<(usize, usize)> {
-        match self {
-            Dir::West => {
-                if col >= 1 {
-                    Some((row, col - 1))
-                } else {
-                    None
-                }
-            }
-            Dir::East => {
-                if col < COLS - 1 {
-                    Some((row, col + 1))
-                } else {
-                    None
-                }
-            }
-            Dir::North => {
-                if row >= 1 {
-                    Some((row - 1, col))
-                } else {
-                    None
-                }
-            }
-            Dir::South => {
-                if row < ROWS - 1 {
-                    Some((row + 1, col))
-                } else {
-                    None
-                }
-            }
-        }
-    }
-}
-
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-struct State {
-    cost: usize,
-    position: Position,
-}
-
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other
-            .cost
-            .cmp(&self.cost)
-            .then_with(|| self.position.cmp(&other.position))
-    }
-}
-
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+/// The memory space with the first kilobyte of `bytes` corrupted, `false` where it is.
+fn memory(bytes: &[Position]) -> Grid<bool> {
+    let mut map = Grid::new(ROWS, COLS, true);
+    for pos in bytes.iter().take(BYTES) {
+        map[*pos] = false;
     }
+    map
 }
 
-type Position = (usize, usize);
-
-fn dijkstra(map: &[[bool; COLS]; ROWS]) -> usize {
-    let start = (0, 0);
+/// A shortest way from the top left corner to the exit, `None` once it is cut off.
+fn escape(map: &Grid<bool>) -> Option<Path<Position>> {
     let end = (ROWS - 1, COLS - 1);
-
-    let mut dist = HashMap::new();
-    for row in 0..ROWS {
-        for col in 0..ROWS {
-            dist.insert((row, col), usize::MAX);
-        }
-    }
-
-    dist.insert(start, 0);
-
-    let mut heap = BinaryHeap::new();
-    heap.push(State {
-        cost: 0,
-        position: start,
-    });
-
-    while let Some(State { cost, position }) = heap.pop() {
-        if position == end {
-            return cost;
-        }
-
-        if cost > *dist.get(&position).unwrap() {
-            continue;
-        }
-
-        for dir in [Dir::North, Dir::East, Dir::South, Dir::West] {
-            if let Some(position_) = dir.offset(position.0, position.1) {
-                if map[position_.0][position_.1] {
-                    let next = State {
-                        cost: cost.saturating_add(1),
-                        position: position_,
-                    };
-
-                    let cur_cost = *dist.get(&next.position).unwrap();
-                    if next.cost < cur_cost {
-                        heap.push(next);
-                        dist.insert(next.position, next.cost);
-                    }
-                }
-            }
-        }
-    }
-    0
+    let moves = |pos| {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |dir| map.offset(pos, dir.step()))
+            .filter(|next| map[*next])
+            .map(|next| (next, 1))
+    };
+    let manhattan = |pos: &Position| end.0 - pos.0 + end.1 - pos.1;
+    pathfinding::astar(&moves, (0, 0), |pos| *pos == end, manhattan)
 }
 
 #[derive(Default)]
@@ -131,33 +39,20 @@ impl AdventOfCodeDay for Solver {
     type Part2Output = String;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        input
-            .lines()
-            .map(|line| {
-                let (x, y) = line.split_once(',').unwrap();
-                (y.parse().unwrap(), x.parse().unwrap())
-            })
-            .collect()
+        Self::try_parse_input(input).unwrap_or_else(|e| panic!("{e}"))
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
-        let mut map = [[true; COLS]; ROWS];
-        for (row, col) in input.iter().take(BYTES) {
-            map[*row][*col] = false;
-        }
-        dijkstra(&map)
+        escape(&memory(input)).map_or(0, |path| path.cost)
     }
 
     fn solve_part2(input: &Self::ParsedInput<'_>) -> Self::Part2Output {
-        let mut map = [[true; COLS]; ROWS];
-        for (row, col) in input.iter().take(BYTES) {
-            map[*row][*col] = false;
-        }
+        let mut map = memory(input);
 
         for i in BYTES..input.len() {
-            map[input[i].0][input[i].1] = false;
+            map[input[i]] = false;
 
-            if dijkstra(&map) == 0 {
+            if escape(&map).is_none() {
                 return format!("{},{}", input[i].1, input[i].0);
             }
         }
@@ -165,12 +60,42 @@ impl AdventOfCodeDay for Solver {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::Solver;
-    use aoc_traits::AdventOfCodeDay;
+impl TryParse for Solver {
+    fn try_parse_input(input: &str) -> Result<Self::ParsedInput<'_>, ParseError> {
+        let src = Source::new(input);
+        src.lines()
+            .map(|line| {
+                let (x, y) = src.split_once(line, ",")?;
+                let coordinate = |s, size| {
+                    let n = src.number::<usize>(s)?;
+                    if n >= size {
+                        return Err(src.error(s, format!("outside the {COLS}x{ROWS} memory space")));
+                    }
+                    Ok(n)
+                };
+                Ok((coordinate(y, ROWS)?, coordinate(x, COLS)?))
+            })
+            .collect()
+    }
+}
+
+/// The memory space after the first kilobyte and the way out of it.
+impl Render for Solver {
+    fn render(input: &Self::ParsedInput<'_>) -> Picture {
+        let map = memory(input);
+        let mut picture = Picture::new(&map, |free| match free {
+            true => Tile::new('.', Color::GRAY),
+            false => Tile::new('#', Color::RED),
+        });
+        if let Some(path) = escape(&map) {
+            picture.overlay(path.nodes, Color::GREEN);
+        }
+        picture
+    }
+}
 
-    const INPUT: &str = "54,47
+impl Example for Solver {
+    const EXAMPLE: &'static str = "54,47
 45,29
 41,65
 55,62
@@ -3621,6 +3546,16 @@ mod tests {
 54,65
 60,54
 ";
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Solver;
+    use aoc_traits::AdventOfCodeDay;
+    use parsing::{Example, TryParse};
+    use render::Render;
+
+    const INPUT: &str = Solver::EXAMPLE;
 
     #[test]
     fn test_part1() {
@@ -3633,4 +3568,101 @@ mod tests {
         let parsed = Solver::parse_input(INPUT);
         assert_eq!(Solver::solve_part2(&parsed), "64,29");
     }
+
+    #[test]
+    fn test_render() {
+        let picture = Solver::render(&Solver::parse_input(INPUT));
+        let text = picture.to_string();
+        assert_eq!(text.matches('#').count(), 1024);
+        let tiles = picture.tiles().iter();
+        // the start as well as every step
+        assert_eq!(tiles.filter(|(_, t)| t.background.is_some()).count(), 283);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let e = Solver::try_parse_input("1,2\n3,71\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 3, "71"));
+    }
+
+    #[test]
+    fn test_input_variants() {
+        for input in parsing::variants(INPUT) {
+            let parsed = Solver::parse_input(&input);
+            assert_eq!(Solver::solve_part1(&parsed), 282);
+        }
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use std::collections::VecDeque;
+
+    use crate::{Solver, BYTES, COLS, ROWS};
+    use aoc_traits::AdventOfCodeDay;
+    use proptest::prelude::*;
+
+    /// Steps from the start to the exit without crossing the first `fallen` bytes, found with a
+    /// breadth-first search.
+    fn steps(bytes: &[(usize, usize)], fallen: usize) -> Option<usize> {
+        let mut corrupted = [[false; COLS]; ROWS];
+        for (x, y) in &bytes[..fallen] {
+            corrupted[*y][*x] = true;
+        }
+        let mut steps = [[None; COLS]; ROWS];
+        steps[0][0] = Some(0);
+        let mut queue = VecDeque::from([(0usize, 0usize)]);
+        while let Some((x, y)) = queue.pop_front() {
+            let next = steps[y][x].map(|s| s + 1);
+            for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+                let (x, y) = (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy));
+                if x < COLS && y < ROWS && !corrupted[y][x] && steps[y][x].is_none() {
+                    steps[y][x] = next;
+                    queue.push_back((x, y));
+                }
+            }
+        }
+        steps[ROWS - 1][COLS - 1]
+    }
+
+    /// Searches again after every byte.
+    fn reference_part2(bytes: &[(usize, usize)]) -> String {
+        (BYTES + 1..=bytes.len())
+            .find(|fallen| steps(bytes, *fallen).is_none())
+            .map(|fallen| format!("{},{}", bytes[fallen - 1].0, bytes[fallen - 1].1))
+            .unwrap_or_default()
+    }
+
+    /// The first kilobyte misses one row and leaves a way out, as in the puzzle. The bytes after
+    /// it fill that row, so the way out gets cut off.
+    fn bytes() -> impl Strategy<Value = Vec<(usize, usize)>> {
+        (1..ROWS - 1)
+            .prop_flat_map(|row| {
+                let scattered = (0..COLS)
+                    .flat_map(|x| (0..ROWS).map(move |y| (x, y)))
+                    .filter(|(x, y)| {
+                        *y != row && (*x, *y) != (0, 0) && (*x, *y) != (COLS - 1, ROWS - 1)
+                    })
+                    .collect::<Vec<_>>();
+                let wall = (0..COLS).map(|x| (x, row)).collect::<Vec<_>>();
+                (Just(scattered).prop_shuffle(), Just(wall).prop_shuffle())
+            })
+            .prop_map(|(scattered, wall)| [&scattered[..BYTES], &wall].concat())
+            .prop_filter("the first kilobyte has to leave a way out", |bytes| {
+                steps(bytes, BYTES).is_some()
+            })
+    }
+
+    proptest! {
+        // part 2 searches the memory space dozens of times per case
+        #![proptest_config(ProptestConfig::with_cases(16))]
+
+        #[test]
+        fn matches_reference(bytes in bytes()) {
+            let input = bytes.iter().map(|(x, y)| format!("{x},{y}\n")).collect::<String>();
+            let parsed = Solver::parse_input(&input);
+            prop_assert_eq!(Solver::solve_part1(&parsed), steps(&bytes, BYTES).unwrap());
+            prop_assert_eq!(Solver::solve_part2(&parsed), reference_part2(&bytes));
+        }
+    }
 }
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use aoc_traits::AdventOfCodeDay;
+use parsing::{Example, ParseError, Source, TryParse};
 
 fn possible<'a>(design: &'a str, patterns: &[&str], cache: &mut HashMap<&'a str, usize>) -> usize {
     if design.is_empty() {
@@ -29,10 +30,7 @@ impl AdventOfCodeDay for Solver {
     type Part2Output = usize;
 
     fn parse_input(input: &str) -> Self::ParsedInput<'_> {
-        let (patterns, designs) = input.split_once("\n\n").unwrap();
-        let patterns = patterns.split(", ").collect();
-        let designs = designs.lines().collect();
-        (patterns, designs)
+        Self::try_parse_input(input).unwrap_or_else(|e| panic!("{e}"))
     }
 
     fn solve_part1(input: &Self::ParsedInput<'_>) -> Self::Part1Output {
@@ -56,12 +54,17 @@ impl AdventOfCodeDay for Solver {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::Solver;
-    use aoc_traits::AdventOfCodeDay;
+impl TryParse for Solver {
+    fn try_parse_input(input: &str) -> Result<Self::ParsedInput<'_>, ParseError> {
+        let (patterns, designs) = Source::new(input).two_sections()?;
+        let patterns = patterns.split(", ").collect();
+        let designs = parsing::lines(designs).collect();
+        Ok((patterns, designs))
+    }
+}
 
-    const INPUT: &str = "r, wr, b, g, bwu, rb, gb, br
+impl Example for Solver {
+    const EXAMPLE: &'static str = "r, wr, b, g, bwu, rb, gb, br
 
 brwrr
 bggr
@@ -72,6 +75,15 @@ bwurrg
 brgr
 bbrgwb
 ";
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Solver;
+    use aoc_traits::AdventOfCodeDay;
+    use parsing::Example;
+
+    const INPUT: &str = Solver::EXAMPLE;
 
     #[test]
     fn test_part1() {
@@ -84,4 +96,50 @@ bbrgwb
         let parsed = Solver::parse_input(INPUT);
         assert_eq!(Solver::solve_part2(&parsed), 16);
     }
+
+    #[test]
+    fn test_input_variants() {
+        for input in parsing::variants(INPUT) {
+            let parsed = Solver::parse_input(&input);
+            assert_eq!(Solver::solve_part1(&parsed), 6);
+        }
+    }
+}
+
+#[cfg(test)]
+mod proptests {
+    use crate::Solver;
+    use aoc_traits::AdventOfCodeDay;
+    use proptest::prelude::*;
+
+    /// Tries every pattern at the start of the design, without remembering anything.
+    fn arrangements(design: &str, patterns: &[String]) -> usize {
+        if design.is_empty() {
+            return 1;
+        }
+        patterns
+            .iter()
+            .filter_map(|pattern| design.strip_prefix(pattern.as_str()))
+            .map(|rest| arrangements(rest, patterns))
+            .sum()
+    }
+
+    /// Distinct patterns, and designs short enough to try every way of arranging them.
+    fn onsen() -> impl Strategy<Value = (Vec<String>, Vec<String>)> {
+        let patterns = prop::collection::btree_set("[wubrg]{1,3}", 1..15);
+        let designs = prop::collection::vec("[wubrg]{1,12}", 1..20);
+        (patterns, designs)
+            .prop_map(|(patterns, designs)| (patterns.into_iter().collect(), designs))
+    }
+
+    proptest! {
+        #[test]
+        fn matches_reference((patterns, designs) in onsen()) {
+            let input = format!("{}\n\n{}\n", patterns.join(", "), designs.join("\n"));
+            let parsed = Solver::parse_input(&input);
+            let counts = designs.iter().map(|design| arrangements(design, &patterns));
+            prop_assert_eq!(Solver::solve_part1(&parsed), counts.clone().filter(|n| *n > 0).count());
+            prop_assert_eq!(Solver::solve_part2(&parsed), counts.sum::<usize>());
+        }
+    }
 }
@@ -0,0 +1,6 @@
+#![no_main]
+
+use fuzz::{inputs::Day01, Parts};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: Day01| fuzz::structured::<day01::Solver>(&input.0, Parts::Both));
@@ -0,0 +1,6 @@
+#![no_main]
+
+use fuzz::{inputs::Day02, Parts};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: Day02| fuzz::structured::<day02::Solver>(&input.0, Parts::Both));
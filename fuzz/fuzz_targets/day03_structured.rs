@@ -0,0 +1,6 @@
+#![no_main]
+
+use fuzz::{inputs::Day03, Parts};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: Day03| fuzz::structured::<day03::Solver>(&input.0, Parts::Both));
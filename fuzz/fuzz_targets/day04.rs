@@ -0,0 +1,6 @@
+#![no_main]
+
+use fuzz::Parts;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| fuzz::text::<day04::Solver>(input, Parts::Both));
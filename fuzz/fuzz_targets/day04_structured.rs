@@ -0,0 +1,6 @@
+#![no_main]
+
+use fuzz::{inputs::Day04, Parts};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: Day04| fuzz::structured::<day04::Solver>(&input.0, Parts::Both));
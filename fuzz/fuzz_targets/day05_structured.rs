@@ -0,0 +1,6 @@
+#![no_main]
+
+use fuzz::{inputs::Day05, Parts};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: Day05| fuzz::structured::<day05::Solver>(&input.0, Parts::Both));
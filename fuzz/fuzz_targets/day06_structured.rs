@@ -0,0 +1,6 @@
+#![no_main]
+
+use fuzz::{inputs::Day06, Parts};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: Day06| fuzz::structured::<day06::Solver>(&input.0, Parts::Both));
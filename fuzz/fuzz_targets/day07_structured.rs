@@ -0,0 +1,6 @@
+#![no_main]
+
+use fuzz::{inputs::Day07, Parts};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: Day07| fuzz::structured::<day07::Solver>(&input.0, Parts::Both));
@@ -0,0 +1,6 @@
+#![no_main]
+
+use fuzz::{inputs::Day08, Parts};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: Day08| fuzz::structured::<day08::Solver>(&input.0, Parts::Both));
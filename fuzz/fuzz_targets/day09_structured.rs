@@ -0,0 +1,6 @@
+#![no_main]
+
+use fuzz::{inputs::Day09, Parts};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: Day09| fuzz::structured::<day09::Solver>(&input.0, Parts::Both));
@@ -0,0 +1,6 @@
+#![no_main]
+
+use fuzz::{inputs::Day10, Parts};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: Day10| fuzz::structured::<day10::Solver>(&input.0, Parts::Both));
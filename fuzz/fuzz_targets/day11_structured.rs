@@ -0,0 +1,6 @@
+#![no_main]
+
+use fuzz::{inputs::Day11, Parts};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: Day11| fuzz::structured::<day11::Solver>(&input.0, Parts::Both));
@@ -0,0 +1,6 @@
+#![no_main]
+
+use fuzz::{inputs::Day12, Parts};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: Day12| fuzz::structured::<day12::Solver>(&input.0, Parts::Both));
@@ -0,0 +1,6 @@
+#![no_main]
+
+use fuzz::{inputs::Day13, Parts};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: Day13| fuzz::structured::<day13::Solver>(&input.0, Parts::Both));
@@ -0,0 +1,7 @@
+#![no_main]
+
+use fuzz::Parts;
+use libfuzzer_sys::fuzz_target;
+
+// Part 2 only stops once the robots draw a picture, which random robots never do.
+fuzz_target!(|input: &str| fuzz::text::<day14::Solver>(input, Parts::Part1));
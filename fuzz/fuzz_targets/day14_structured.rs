@@ -0,0 +1,7 @@
+#![no_main]
+
+use fuzz::{inputs::Day14, Parts};
+use libfuzzer_sys::fuzz_target;
+
+// Part 2 only stops once the robots draw a picture, which random robots never do.
+fuzz_target!(|input: Day14| fuzz::structured::<day14::Solver>(&input.0, Parts::Part1));
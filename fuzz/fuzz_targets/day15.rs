@@ -0,0 +1,7 @@
+#![no_main]
+
+use fuzz::Parts;
+use libfuzzer_sys::fuzz_target;
+
+// Part 2 is still `todo!()`.
+fuzz_target!(|input: &str| fuzz::text::<day15::Solver>(input, Parts::Part1));
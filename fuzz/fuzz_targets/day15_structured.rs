@@ -0,0 +1,7 @@
+#![no_main]
+
+use fuzz::{inputs::Day15, Parts};
+use libfuzzer_sys::fuzz_target;
+
+// Part 2 is still `todo!()`.
+fuzz_target!(|input: Day15| fuzz::structured::<day15::Solver>(&input.0, Parts::Part1));
@@ -0,0 +1,6 @@
+#![no_main]
+
+use fuzz::{inputs::Day16, Parts};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: Day16| fuzz::structured::<day16::Solver>(&input.0, Parts::Both));
@@ -0,0 +1,7 @@
+#![no_main]
+
+use fuzz::Parts;
+use libfuzzer_sys::fuzz_target;
+
+// Arbitrary programs can jump back forever, the structured target runs ones that end.
+fuzz_target!(|input: &str| fuzz::text::<day17::Solver>(input, Parts::Neither));
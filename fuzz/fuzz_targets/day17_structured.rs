@@ -0,0 +1,7 @@
+#![no_main]
+
+use fuzz::{inputs::Day17, Parts};
+use libfuzzer_sys::fuzz_target;
+
+// Part 2 is still `todo!()`.
+fuzz_target!(|input: Day17| fuzz::structured::<day17::Solver>(&input.0, Parts::Part1));
@@ -0,0 +1,6 @@
+#![no_main]
+
+use fuzz::{inputs::Day18, Parts};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: Day18| fuzz::structured::<day18::Solver>(&input.0, Parts::Both));
@@ -0,0 +1,6 @@
+#![no_main]
+
+use fuzz::{inputs::Day19, Parts};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: Day19| fuzz::structured::<day19::Solver>(&input.0, Parts::Both));
@@ -0,0 +1,347 @@
+//! Generators of puzzle inputs in the format, and roughly the value ranges, of the real ones.
+
+use std::{
+    fmt::{self, Debug, Write},
+    ops::RangeInclusive,
+};
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+macro_rules! input {
+    ($(#[$doc:meta])* $name:ident, $generate:ident) => {
+        $(#[$doc])*
+        pub struct $name(pub String);
+
+        impl<'a> Arbitrary<'a> for $name {
+            fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                $generate(u).map($name)
+            }
+        }
+
+        /// Prints the input as it would appear in a file, so crashes are easy to reproduce.
+        impl Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+    };
+}
+
+/// `count` lines made by `line`, each ending in a newline.
+fn lines(
+    u: &mut Unstructured,
+    count: RangeInclusive<usize>,
+    mut line: impl FnMut(&mut Unstructured) -> Result<String>,
+) -> Result<String> {
+    let mut out = String::new();
+    for _ in 0..u.int_in_range(count)? {
+        out.push_str(&line(u)?);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+fn join<T: ToString>(items: impl IntoIterator<Item = T>, separator: &str) -> String {
+    items
+        .into_iter()
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+fn grid(
+    u: &mut Unstructured,
+    rows: RangeInclusive<usize>,
+    cols: RangeInclusive<usize>,
+    cells: &[u8],
+) -> Result<Vec<Vec<u8>>> {
+    let rows = u.int_in_range(rows)?;
+    let cols = u.int_in_range(cols)?;
+    (0..rows)
+        .map(|_| (0..cols).map(|_| u.choose(cells).copied()).collect())
+        .collect()
+}
+
+/// Surrounds `grid` with walls, as the maze puzzles do.
+fn walled(grid: &mut [Vec<u8>]) {
+    let rows = grid.len();
+    for (r, row) in grid.iter_mut().enumerate() {
+        let cols = row.len();
+        for (c, cell) in row.iter_mut().enumerate() {
+            if r == 0 || c == 0 || r == rows - 1 || c == cols - 1 {
+                *cell = b'#';
+            }
+        }
+    }
+}
+
+/// Puts `cell` somewhere not on the outermost `margin` rows and columns, on a cell that is not in
+/// `taken`.
+fn place(
+    u: &mut Unstructured,
+    grid: &mut [Vec<u8>],
+    margin: usize,
+    cell: u8,
+    taken: &[u8],
+) -> Result<()> {
+    let free = grid
+        .iter()
+        .enumerate()
+        .skip(margin)
+        .take(grid.len().saturating_sub(2 * margin))
+        .flat_map(|(r, row)| {
+            (margin..row.len().saturating_sub(margin))
+                .filter(|c| !taken.contains(&row[*c]))
+                .map(move |c| (r, c))
+        })
+        .collect::<Vec<_>>();
+    let (r, c) = *u.choose(&free)?;
+    grid[r][c] = cell;
+    Ok(())
+}
+
+fn render(grid: &[Vec<u8>]) -> String {
+    let mut out = String::new();
+    for row in grid {
+        out.push_str(std::str::from_utf8(row).expect("grids are ASCII"));
+        out.push('\n');
+    }
+    out
+}
+
+fn day01(u: &mut Unstructured) -> Result<String> {
+    lines(u, 1..=1000, |u| {
+        let left = u.int_in_range(10000..=99999u32)?;
+        let right = u.int_in_range(10000..=99999u32)?;
+        Ok(format!("{left}   {right}"))
+    })
+}
+
+fn day02(u: &mut Unstructured) -> Result<String> {
+    lines(u, 1..=1000, |u| {
+        let levels = (0..u.int_in_range(1..=8)?)
+            .map(|_| u.int_in_range(1..=99u32))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(join(levels, " "))
+    })
+}
+
+fn day03(u: &mut Unstructured) -> Result<String> {
+    let mut out = String::new();
+    for _ in 0..u.int_in_range(1..=200)? {
+        match u.int_in_range(0..=4)? {
+            0 | 1 => {
+                let a = u.int_in_range(0..=999)?;
+                let b = u.int_in_range(0..=999)?;
+                write!(out, "mul({a},{b})").unwrap();
+            }
+            2 => out.push_str("do()"),
+            3 => out.push_str("don't()"),
+            _ => out.push(*u.choose(&['m', 'u', 'l', '(', ')', ',', '1', ' ', '\'', 'x', '\n'])?),
+        }
+    }
+    Ok(out)
+}
+
+fn day04(u: &mut Unstructured) -> Result<String> {
+    Ok(render(&grid(u, 1..=30, 1..=30, b"XMAS")?))
+}
+
+fn day05(u: &mut Unstructured) -> Result<String> {
+    let pages = (10..=99u32).collect::<Vec<_>>();
+    let mut out = lines(u, 1..=200, |u| {
+        let a = u.choose(&pages)?;
+        let b = u.choose(&pages)?;
+        Ok(format!("{a}|{b}"))
+    })?;
+    out.push('\n');
+    out.push_str(&lines(u, 1..=50, |u| {
+        let len = 2 * u.int_in_range(0..=11)? + 1;
+        let update = (0..len)
+            .map(|_| u.choose(&pages).copied())
+            .collect::<Result<Vec<_>>>()?;
+        Ok(join(update, ","))
+    })?);
+    Ok(out)
+}
+
+fn day06(u: &mut Unstructured) -> Result<String> {
+    let mut map = grid(u, 1..=30, 1..=30, b"....#")?;
+    let guard = *u.choose(b"^v<>")?;
+    place(u, &mut map, 0, guard, b"#")?;
+    Ok(render(&map))
+}
+
+fn day07(u: &mut Unstructured) -> Result<String> {
+    lines(u, 1..=100, |u| {
+        let result = u.int_in_range(0..=1_000_000_000_000u64)?;
+        let terms = (0..u.int_in_range(1..=10)?)
+            .map(|_| u.int_in_range(0..=999u64))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(format!("{result}: {}", join(terms, " ")))
+    })
+}
+
+fn day08(u: &mut Unstructured) -> Result<String> {
+    Ok(render(&grid(u, 1..=50, 1..=50, b"..........aA0")?))
+}
+
+fn day09(u: &mut Unstructured) -> Result<String> {
+    let len = u.int_in_range(1..=2000)?;
+    let mut out = (0..len)
+        .map(|_| u.int_in_range(b'0'..=b'9').map(char::from))
+        .collect::<Result<String>>()?;
+    out.push('\n');
+    Ok(out)
+}
+
+fn day10(u: &mut Unstructured) -> Result<String> {
+    Ok(render(&grid(u, 1..=40, 1..=40, b"0123456789")?))
+}
+
+fn day11(u: &mut Unstructured) -> Result<String> {
+    let stones = (0..u.int_in_range(1..=10)?)
+        .map(|_| u.int_in_range(0..=9_999_999u64))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(format!("{}\n", join(stones, " ")))
+}
+
+fn day12(u: &mut Unstructured) -> Result<String> {
+    Ok(render(&grid(u, 1..=40, 1..=40, b"ABCDE")?))
+}
+
+fn day13(u: &mut Unstructured) -> Result<String> {
+    let mut claws = vec![];
+    for _ in 0..u.int_in_range(1..=50)? {
+        let mut button =
+            || -> Result<(u32, u32)> { Ok((u.int_in_range(1..=99)?, u.int_in_range(1..=99)?)) };
+        let (ax, ay) = button()?;
+        let (bx, by) = button()?;
+        let px = u.int_in_range(0..=20000u32)?;
+        let py = u.int_in_range(0..=20000u32)?;
+        claws.push(format!(
+            "Button A: X+{ax}, Y+{ay}\nButton B: X+{bx}, Y+{by}\nPrize: X={px}, Y={py}\n"
+        ));
+    }
+    Ok(claws.join("\n"))
+}
+
+fn day14(u: &mut Unstructured) -> Result<String> {
+    lines(u, 1..=500, |u| {
+        let x = u.int_in_range(0..=100)?;
+        let y = u.int_in_range(0..=102)?;
+        let vx = u.int_in_range(-100..=100)?;
+        let vy = u.int_in_range(-100..=100)?;
+        Ok(format!("p={x},{y} v={vx},{vy}"))
+    })
+}
+
+fn day15(u: &mut Unstructured) -> Result<String> {
+    let mut map = grid(u, 3..=20, 3..=20, b"...O#")?;
+    walled(&mut map);
+    place(u, &mut map, 1, b'@', b"")?;
+    let mut out = render(&map);
+    out.push('\n');
+    out.push_str(&lines(u, 1..=10, |u| {
+        (0..u.int_in_range(1..=70)?)
+            .map(|_| u.choose(&['<', '>', '^', 'v']).copied())
+            .collect()
+    })?);
+    Ok(out)
+}
+
+fn day16(u: &mut Unstructured) -> Result<String> {
+    let mut map = grid(u, 4..=30, 4..=30, b"...#")?;
+    walled(&mut map);
+    place(u, &mut map, 1, b'S', b"")?;
+    place(u, &mut map, 1, b'E', b"S")?;
+    Ok(render(&map))
+}
+
+fn day17(u: &mut Unstructured) -> Result<String> {
+    let a = u.int_in_range(0..=1u64 << 48)?;
+    let b = u.int_in_range(0..=7u64)?;
+    let c = u.int_in_range(0..=7u64)?;
+    // Like the puzzle's programs, a loop without other jumps that shifts A down, prints and
+    // jumps back, so it always ends. Combo operands are never the reserved 7.
+    let mut program = vec![];
+    for _ in 0..u.int_in_range(0..=6)? {
+        let opcode = *u.choose(&[0u8, 1, 2, 4, 5, 6, 7])?;
+        let literal = matches!(opcode, 1 | 4);
+        program.extend([opcode, u.int_in_range(0..=if literal { 7 } else { 6 })?]);
+    }
+    program.extend([0, 3, 5, u.int_in_range(0..=6)?, 3, 0]);
+    Ok(format!(
+        "Register A: {a}\nRegister B: {b}\nRegister C: {c}\n\nProgram: {}\n",
+        join(program, ",")
+    ))
+}
+
+fn day18(u: &mut Unstructured) -> Result<String> {
+    // The solver drops the first 1024 bytes into a 71x71 memory space, so those have to leave
+    // the start and the exit free.
+    let mut bytes = (0..=70)
+        .flat_map(|x| (0..=70).map(move |y| (x, y)))
+        .filter(|pos| !matches!(pos, (0, 0) | (70, 70)))
+        .collect::<Vec<_>>();
+    let count = u.int_in_range(1024..=2500)?;
+    for i in 0..count {
+        let j = u.int_in_range(i..=bytes.len() - 1)?;
+        bytes.swap(i, j);
+    }
+    let mut out = String::new();
+    for (x, y) in &bytes[..count] {
+        writeln!(out, "{x},{y}").unwrap();
+    }
+    Ok(out)
+}
+
+fn towel(u: &mut Unstructured, len: RangeInclusive<usize>) -> Result<String> {
+    (0..u.int_in_range(len)?)
+        .map(|_| u.choose(&['w', 'u', 'b', 'r', 'g']).copied())
+        .collect()
+}
+
+fn day19(u: &mut Unstructured) -> Result<String> {
+    let patterns = (0..u.int_in_range(1..=50)?)
+        .map(|_| towel(u, 1..=8))
+        .collect::<Result<Vec<_>>>()?;
+    let designs = lines(u, 1..=50, |u| towel(u, 1..=60))?;
+    Ok(format!("{}\n\n{designs}", patterns.join(", ")))
+}
+
+input!(Day01, day01);
+input!(Day02, day02);
+input!(
+    /// Corrupted memory with `mul`, `do` and `don't` instructions between stray characters.
+    Day03,
+    day03
+);
+input!(Day04, day04);
+input!(Day05, day05);
+input!(Day06, day06);
+input!(
+    /// Terms may be zero, which the puzzle never has.
+    Day07,
+    day07
+);
+input!(Day08, day08);
+input!(Day09, day09);
+input!(Day10, day10);
+input!(Day11, day11);
+input!(Day12, day12);
+input!(Day13, day13);
+input!(Day14, day14);
+input!(Day15, day15);
+input!(
+    /// The end is not necessarily reachable from the start.
+    Day16,
+    day16
+);
+input!(Day17, day17);
+input!(
+    /// Up to 2500 distinct bytes, the exit does not necessarily get cut off.
+    Day18,
+    day18
+);
+input!(Day19, day19);
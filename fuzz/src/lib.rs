@@ -0,0 +1,42 @@
+//! Shared code of the fuzz targets. Every day has two: `dayNN` feeds it arbitrary text, which
+//! exercises the parser, and `dayNN_structured` feeds it inputs generated in the puzzle's format,
+//! which get past the parser and into the solvers. Run them with
+//! `cargo +nightly fuzz run day07_structured` from this directory.
+
+use aoc_traits::AdventOfCodeDay;
+use parsing::TryParse;
+
+pub mod inputs;
+
+/// Which parts a target solves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parts {
+    Both,
+    /// For days whose part 2 is not done yet.
+    Part1,
+    /// Only parses, for days whose solvers need not stop on inputs the puzzle never gives.
+    Neither,
+}
+
+fn solve<D: AdventOfCodeDay>(parsed: &D::ParsedInput<'_>, parts: Parts) {
+    if parts != Parts::Neither {
+        std::hint::black_box(D::solve_part1(parsed));
+    }
+    if parts == Parts::Both {
+        std::hint::black_box(D::solve_part2(parsed));
+    }
+}
+
+/// Parsers must reject anything malformed with a `ParseError` rather than a panic, and the
+/// solvers must cope with whatever they accept.
+pub fn text<D: TryParse>(input: &str, parts: Parts) {
+    if let Ok(parsed) = D::try_parse_input(input) {
+        solve::<D>(&parsed, parts);
+    }
+}
+
+/// Generated inputs follow the puzzle's format, so the parser has to accept them.
+pub fn structured<D: TryParse>(input: &str, parts: Parts) {
+    let parsed = D::try_parse_input(input).unwrap_or_else(|e| panic!("rejected valid input: {e}"));
+    solve::<D>(&parsed, parts);
+}
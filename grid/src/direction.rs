@@ -0,0 +1,196 @@
+use crate::Position;
+
+/// One of the four orthogonal directions, north is up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// Clockwise from north.
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// `^`, `>`, `v` or `<`.
+    pub fn from_arrow(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Direction::North),
+            '>' => Some(Direction::East),
+            'v' => Some(Direction::South),
+            '<' => Some(Direction::West),
+            _ => None,
+        }
+    }
+
+    /// The arrow [`Direction::from_arrow`] parses, e.g. to draw a guard.
+    pub fn arrow(self) -> char {
+        ['^', '>', 'v', '<'][self as usize]
+    }
+
+    /// `N`, `E`, `S` or `W`.
+    pub fn from_letter(c: char) -> Option<Self> {
+        match c {
+            'N' => Some(Direction::North),
+            'E' => Some(Direction::East),
+            'S' => Some(Direction::South),
+            'W' => Some(Direction::West),
+            _ => None,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    /// The `(row, column)` change of a step, to use with [`Grid::offset`](crate::Grid::offset).
+    pub fn step(self) -> (isize, isize) {
+        Direction8::from(self).step()
+    }
+
+    /// The position a step away, `None` if that is above the first row or left of the first
+    /// column.
+    pub fn checked_offset(self, pos: Position) -> Option<Position> {
+        Direction8::from(self).checked_offset(pos)
+    }
+
+    /// The position a step away, for maps with a wall around them that is never stepped over.
+    /// Panics if that is above the first row or left of the first column.
+    pub fn offset(self, pos: Position) -> Position {
+        Direction8::from(self).offset(pos)
+    }
+}
+
+/// One of the four orthogonal or four diagonal directions, north is up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    /// Clockwise from north.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+    ];
+
+    /// `N`, `NE`, `E`, `SE`, `S`, `SW`, `W` or `NW`.
+    pub fn from_letters(s: &str) -> Option<Self> {
+        match s {
+            "N" => Some(Direction8::North),
+            "NE" => Some(Direction8::NorthEast),
+            "E" => Some(Direction8::East),
+            "SE" => Some(Direction8::SouthEast),
+            "S" => Some(Direction8::South),
+            "SW" => Some(Direction8::SouthWest),
+            "W" => Some(Direction8::West),
+            "NW" => Some(Direction8::NorthWest),
+            _ => None,
+        }
+    }
+
+    /// An eighth of a turn clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// An eighth of a turn counterclockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    /// The `(row, column)` change of a step, to use with [`Grid::offset`](crate::Grid::offset).
+    pub fn step(self) -> (isize, isize) {
+        crate::NEIGHBORS8[self as usize]
+    }
+
+    /// The position a step away, `None` if that is above the first row or left of the first
+    /// column.
+    pub fn checked_offset(self, (row, col): Position) -> Option<Position> {
+        let (dr, dc) = self.step();
+        Some((row.checked_add_signed(dr)?, col.checked_add_signed(dc)?))
+    }
+
+    /// The position a step away, for maps with a wall around them that is never stepped over.
+    /// Panics if that is above the first row or left of the first column.
+    pub fn offset(self, pos: Position) -> Position {
+        self.checked_offset(pos)
+            .unwrap_or_else(|| panic!("a step {self:?} from {pos:?} leaves the grid"))
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(dir: Direction) -> Self {
+        Direction8::ALL[dir as usize * 2]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Direction, Direction8, NEIGHBORS4};
+
+    #[test]
+    fn test_turns() {
+        for dir in Direction::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(dir.turn_right().turn_right(), dir.reverse());
+            assert_eq!(dir.reverse().reverse(), dir);
+        }
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction8::North.turn_left(), Direction8::NorthWest);
+        assert_eq!(Direction8::SouthEast.reverse(), Direction8::NorthWest);
+    }
+
+    #[test]
+    fn test_offsets() {
+        assert_eq!(Direction::ALL.map(Direction::step), NEIGHBORS4);
+        assert_eq!(Direction::West.checked_offset((3, 0)), None);
+        assert_eq!(Direction::South.offset((3, 0)), (4, 0));
+        assert_eq!(Direction8::NorthEast.checked_offset((0, 5)), None);
+        assert_eq!(Direction8::SouthWest.offset((2, 5)), (3, 4));
+    }
+
+    #[test]
+    fn test_parse() {
+        let arrows = "^>v<".chars().map(Direction::from_arrow);
+        assert!(arrows.map(Option::unwrap).eq(Direction::ALL));
+        assert_eq!(Direction::ALL.map(Direction::arrow), ['^', '>', 'v', '<']);
+        let letters = "NESW".chars().map(Direction::from_letter);
+        assert!(letters.map(Option::unwrap).eq(Direction::ALL));
+        assert_eq!(Direction::from_arrow('N'), None);
+        assert_eq!(Direction8::from_letters("SW"), Some(Direction8::SouthWest));
+        assert_eq!(Direction8::from_letters("WS"), None);
+    }
+}
@@ -0,0 +1,297 @@
+use std::{
+    fmt::Display,
+    iter::StepBy,
+    ops::{Index, IndexMut},
+    slice,
+};
+
+use parsing::{ParseError, Source};
+
+mod direction;
+
+pub use direction::{Direction, Direction8};
+
+/// `(row, column)`, counted from the top left corner.
+pub type Position = (usize, usize);
+
+/// Steps to the orthogonal neighbors, clockwise from up.
+pub const NEIGHBORS4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// Steps to the orthogonal and diagonal neighbors, clockwise from up.
+pub const NEIGHBORS8: [(isize, isize); 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+/// A rectangular map of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    rows: usize,
+    cols: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(rows: usize, cols: usize, cell: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            rows,
+            cols,
+            cells: vec![cell; rows * cols],
+        }
+    }
+
+    pub fn from_fn(rows: usize, cols: usize, mut cell: impl FnMut(Position) -> T) -> Self {
+        let cells = (0..rows)
+            .flat_map(|row| (0..cols).map(move |col| (row, col)))
+            .map(&mut cell)
+            .collect();
+        Grid { rows, cols, cells }
+    }
+
+    /// Parses every line of `text`, a slice of the input of `src`, into a row with a cell per
+    /// character. `cell` gets the position and character and rejects it with a message.
+    pub fn parse<E: Display>(
+        src: &Source,
+        text: &str,
+        mut cell: impl FnMut(Position, char) -> Result<T, E>,
+    ) -> Result<Self, ParseError> {
+        let mut rows = 0;
+        let mut cols = None;
+        let mut cells = Vec::with_capacity(text.len());
+        for line in parsing::lines(text) {
+            let len = cells.len();
+            for (col, (i, c)) in line.char_indices().enumerate() {
+                cells.push(cell((rows, col), c).map_err(|e| src.error_at(line, i, e))?);
+            }
+            let width = cells.len() - len;
+            match cols {
+                Some(cols) if cols != width => {
+                    return Err(src.error(line, format_args!("expected {cols} columns")));
+                }
+                _ => cols = Some(width),
+            }
+            rows += 1;
+        }
+        match cols {
+            Some(cols) if cols > 0 => Ok(Grid { rows, cols, cells }),
+            _ => Err(src.error(text, "empty map")),
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn contains(&self, (row, col): Position) -> bool {
+        row < self.rows && col < self.cols
+    }
+
+    pub fn get(&self, pos: Position) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.0 * self.cols + pos.1])
+    }
+
+    pub fn get_mut(&mut self, pos: Position) -> Option<&mut T> {
+        self.contains(pos)
+            .then(|| &mut self.cells[pos.0 * self.cols + pos.1])
+    }
+
+    /// The position `step` away from `pos`, if that is still on the grid.
+    pub fn offset(&self, (row, col): Position, (dr, dc): (isize, isize)) -> Option<Position> {
+        let pos = (row.checked_add_signed(dr)?, col.checked_add_signed(dc)?);
+        self.contains(pos).then_some(pos)
+    }
+
+    /// Where `(row, col)` ends up on a grid that repeats in every direction.
+    pub fn wrap(&self, row: isize, col: isize) -> Position {
+        (
+            row.rem_euclid(self.rows as isize) as usize,
+            col.rem_euclid(self.cols as isize) as usize,
+        )
+    }
+
+    /// The position `step` away from `pos`, coming back in on the other side at the edges.
+    pub fn offset_wrapping(&self, (row, col): Position, (dr, dc): (isize, isize)) -> Position {
+        self.wrap(row as isize + dr, col as isize + dc)
+    }
+
+    pub fn neighbors4(&self, pos: Position) -> impl Iterator<Item = Position> + '_ {
+        NEIGHBORS4
+            .into_iter()
+            .filter_map(move |step| self.offset(pos, step))
+    }
+
+    pub fn neighbors8(&self, pos: Position) -> impl Iterator<Item = Position> + '_ {
+        NEIGHBORS8
+            .into_iter()
+            .filter_map(move |step| self.offset(pos, step))
+    }
+
+    /// The positions after `pos` in steps of `step` up to the edge, i.e. part of a row, column or
+    /// diagonal.
+    pub fn ray(&self, pos: Position, step: (isize, isize)) -> impl Iterator<Item = Position> + '_ {
+        std::iter::successors(self.offset(pos, step), move |pos| self.offset(*pos, step))
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let cols = self.cols;
+        (0..self.rows).flat_map(move |row| (0..cols).map(move |col| (row, col)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn column(&self, col: usize) -> StepBy<slice::Iter<'_, T>> {
+        assert!(
+            col < self.cols,
+            "column {col} is outside the {}x{} grid",
+            self.rows,
+            self.cols
+        );
+        self.cells[col..].iter().step_by(self.cols)
+    }
+
+    /// The first position, row by row, whose cell matches.
+    pub fn position(&self, mut matches: impl FnMut(&T) -> bool) -> Option<Position> {
+        let i = self.cells.iter().position(&mut matches)?;
+        Some((i / self.cols, i % self.cols))
+    }
+
+    pub fn map<U>(&self, cell: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            rows: self.rows,
+            cols: self.cols,
+            cells: self.cells.iter().map(cell).collect(),
+        }
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Position) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the {}x{} grid", self.rows, self.cols))
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, pos: Position) -> &mut T {
+        let (rows, cols) = (self.rows, self.cols);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the {rows}x{cols} grid"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use crate::Grid;
+    use parsing::Source;
+
+    const INPUT: &str = "abc\ndef\n";
+
+    fn grid() -> Grid<char> {
+        Grid::parse(&Source::new(INPUT), INPUT, |_, c| Ok::<_, Infallible>(c)).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = grid();
+        assert_eq!((grid.rows(), grid.cols()), (2, 3));
+        assert_eq!(grid[(1, 0)], 'd');
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(grid.column(2).collect::<String>(), "cf");
+        assert_eq!(grid.position(|c| *c == 'e'), Some((1, 1)));
+
+        for input in parsing::variants(INPUT) {
+            let src = Source::new(&input);
+            let parsed = Grid::parse(&src, &input, |_, c| Ok::<_, Infallible>(c)).unwrap();
+            assert_eq!(parsed, grid);
+        }
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "ab\nabc\n";
+        let e = Grid::parse(&Source::new(input), input, |_, c| Ok::<_, Infallible>(c)).unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.message.as_str()),
+            (2, 1, "expected 2 columns")
+        );
+
+        let input = "ab\naxb\n";
+        let e = Grid::parse(&Source::new(input), input, |_, c| match c {
+            'x' => Err("unexpected x"),
+            c => Ok(c),
+        })
+        .unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 2, "x"));
+
+        let e = Grid::parse(&Source::new("\n"), "\n", |_, c| Ok::<_, Infallible>(c)).unwrap_err();
+        assert_eq!(e.message, "empty map");
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = grid();
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.offset((0, 0), (1, 2)), Some((1, 2)));
+        assert_eq!(grid.offset((1, 2), (0, 1)), None);
+        assert_eq!(
+            grid.neighbors4((0, 1)).collect::<Vec<_>>(),
+            [(0, 2), (1, 1), (0, 0)]
+        );
+        assert_eq!(grid.neighbors8((0, 0)).count(), 3);
+        assert_eq!(grid.neighbors8((1, 1)).count(), 5);
+    }
+
+    #[test]
+    fn test_wrapping() {
+        let grid = grid();
+        assert_eq!(grid.wrap(-1, 3), (1, 0));
+        assert_eq!(grid.wrap(5, -7), (1, 2));
+        assert_eq!(grid.offset_wrapping((1, 2), (1, 1)), (0, 0));
+    }
+
+    #[test]
+    fn test_ray() {
+        let grid = Grid::from_fn(4, 4, |(row, col)| row * 4 + col);
+        let values = |pos, step| grid.ray(pos, step).map(|p| grid[p]).collect::<Vec<_>>();
+        assert_eq!(values((0, 0), (1, 1)), [5, 10, 15]);
+        assert_eq!(values((3, 0), (-1, 1)), [9, 6, 3]);
+        assert_eq!(values((2, 3), (0, -1)), [10, 9, 8]);
+        assert_eq!(values((0, 0), (-1, 0)), []);
+    }
+
+    #[test]
+    #[should_panic = "column 3 is outside the 2x3 grid"]
+    fn test_column_out_of_bounds() {
+        // would otherwise yield cells of the next row
+        grid().column(3).for_each(drop);
+    }
+}
@@ -0,0 +1,191 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fmt::Display,
+    hint::black_box,
+};
+
+use parsing::{Example, ParseError, TryParse};
+
+use crate::{Answers, DayVisitor};
+
+thread_local! {
+    static COUNTERS: Counters = const {
+        Counters {
+            enabled: Cell::new(false),
+            allocations: Cell::new(0),
+            bytes: Cell::new(0),
+            live: Cell::new(0),
+            peak: Cell::new(0),
+        }
+    };
+}
+
+/// Per-thread, so days solved on other threads do not show up in each other's numbers.
+struct Counters {
+    enabled: Cell<bool>,
+    allocations: Cell<u64>,
+    bytes: Cell<u64>,
+    /// Can go negative when memory allocated before [`measure`] is freed during it.
+    live: Cell<i64>,
+    peak: Cell<i64>,
+}
+
+impl Counters {
+    fn alloc(&self, size: usize) {
+        if self.enabled.get() {
+            self.allocations.set(self.allocations.get() + 1);
+            self.bytes.set(self.bytes.get() + size as u64);
+            self.live.set(self.live.get() + size as i64);
+            self.peak.set(self.peak.get().max(self.live.get()));
+        }
+    }
+
+    fn dealloc(&self, size: usize) {
+        if self.enabled.get() {
+            self.live.set(self.live.get() - size as i64);
+        }
+    }
+}
+
+/// [`System`], counting the allocations of the threads that are inside [`measure`]. The binary
+/// installs it with `#[global_allocator]`; without it every [`AllocStats`] is zero.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let _ = COUNTERS.try_with(|c| c.alloc(layout.size()));
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            let _ = COUNTERS.try_with(|c| c.alloc(layout.size()));
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        let _ = COUNTERS.try_with(|c| c.dealloc(layout.size()));
+    }
+
+    /// Counted as a new allocation of `new_size` and freeing the old one.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            let _ = COUNTERS.try_with(|c| {
+                c.dealloc(layout.size());
+                c.alloc(new_size);
+            });
+        }
+        new
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: u64,
+    pub bytes: u64,
+    /// Most bytes allocated during the phase that were live at the same time.
+    pub peak: u64,
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} bytes, {} peak",
+            self.allocations, self.bytes, self.peak
+        )
+    }
+}
+
+/// Runs `f` and counts the allocations it makes on the current thread.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, AllocStats) {
+    COUNTERS.with(|c| {
+        c.allocations.set(0);
+        c.bytes.set(0);
+        c.live.set(0);
+        c.peak.set(0);
+        c.enabled.set(true);
+    });
+    let result = f();
+    let stats = COUNTERS.with(|c| {
+        c.enabled.set(false);
+        AllocStats {
+            allocations: c.allocations.get(),
+            bytes: c.bytes.get(),
+            peak: c.peak.get().max(0) as u64,
+        }
+    });
+    (result, stats)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocReport {
+    pub parse: AllocStats,
+    pub part1: AllocStats,
+    pub part2: AllocStats,
+}
+
+/// Solves a day once, counting the allocations of parse, part 1 and part 2 separately. Turning the
+/// answers into strings is not counted.
+pub(crate) struct CountAllocs<'a>(pub &'a str);
+
+impl DayVisitor for CountAllocs<'_> {
+    type Output = Result<(Answers, AllocReport), ParseError>;
+
+    fn visit<D: TryParse + Example + 'static>(self, day: usize) -> Self::Output {
+        let (parsed, parse) = measure(|| D::try_parse_input(black_box(self.0)));
+        let parsed = parsed.map_err(|e| e.with_day(day))?;
+        let (p1, part1) = measure(|| black_box(D::solve_part1(&parsed)));
+        let (p2, part2) = measure(|| black_box(D::solve_part2(&parsed)));
+        Ok((
+            Answers {
+                part1: p1.to_string(),
+                part2: p2.to_string(),
+            },
+            AllocReport {
+                parse,
+                part1,
+                part2,
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::alloc::{measure, AllocStats, CountingAllocator};
+
+    #[global_allocator]
+    static ALLOC: CountingAllocator = CountingAllocator;
+
+    #[test]
+    fn test_measure() {
+        let kept = vec![0u8; 10];
+        let ((), stats) = measure(|| {
+            let mut v = Vec::<u8>::with_capacity(100);
+            v.push(1);
+            drop(v);
+            let _ = std::hint::black_box(vec![0u8; 40]);
+            drop(kept);
+        });
+        assert_eq!(
+            stats,
+            AllocStats {
+                allocations: 2,
+                bytes: 140,
+                peak: 100,
+            }
+        );
+
+        let (_, stats) = measure(|| vec![1u64; 4]);
+        assert_eq!((stats.allocations, stats.bytes, stats.peak), (1, 32, 32));
+    }
+}
@@ -0,0 +1,145 @@
+use std::{collections::BTreeMap, path::Path, time::Duration};
+
+use color_eyre::{eyre::eyre, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{crypt::write_atomic, timing::Timings};
+
+/// Slowdowns smaller than this are measurement noise, however large they are relatively.
+pub const NOISE_FLOOR: Duration = Duration::from_micros(5);
+
+pub const PHASES: [&str; 3] = ["parse", "part1", "part2"];
+
+/// Median time of every phase of every benchmarked day, as stored in the baseline JSON file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub iterations: usize,
+    pub days: BTreeMap<usize, PhaseNanos>,
+}
+
+/// Median nanoseconds per phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PhaseNanos {
+    pub parse: u64,
+    pub part1: u64,
+    pub part2: u64,
+}
+
+impl PhaseNanos {
+    pub fn phases(&self) -> [Duration; 3] {
+        [self.parse, self.part1, self.part2].map(Duration::from_nanos)
+    }
+}
+
+impl From<&Timings> for PhaseNanos {
+    fn from(timings: &Timings) -> Self {
+        let nanos = |d: Duration| d.as_nanos().try_into().unwrap_or(u64::MAX);
+        PhaseNanos {
+            parse: nanos(timings.parse.median),
+            part1: nanos(timings.part1.median),
+            part2: nanos(timings.part2.median),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Change {
+    pub day: usize,
+    pub phase: &'static str,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl Change {
+    /// Relative change, `0.1` meaning 10% slower.
+    pub fn relative(&self) -> f64 {
+        self.current.as_secs_f64() / self.baseline.as_secs_f64().max(f64::MIN_POSITIVE) - 1.0
+    }
+
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.current.saturating_sub(self.baseline) > NOISE_FLOOR && self.relative() > threshold
+    }
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = std::fs::read_to_string(path).map_err(|e| eyre!("{}: {e}", path.display()))?;
+        serde_json::from_str(&json).map_err(|e| eyre!("{}: {e}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        write_atomic(path, serde_json::to_string_pretty(self)?.as_bytes())
+    }
+
+    /// Every phase of the days that were measured in both runs.
+    pub fn compare(&self, current: &Baseline) -> Vec<Change> {
+        current
+            .days
+            .iter()
+            .filter_map(|(day, now)| Some((*day, self.days.get(day)?, now)))
+            .flat_map(|(day, before, now)| {
+                PHASES
+                    .into_iter()
+                    .zip(before.phases().into_iter().zip(now.phases()))
+                    .map(move |(phase, (baseline, current))| Change {
+                        day,
+                        phase,
+                        baseline,
+                        current,
+                    })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::baseline::{Baseline, PhaseNanos};
+
+    /// A run with the given parse, part 1 and part 2 times in microseconds.
+    fn run(days: &[(usize, [u64; 3])]) -> Baseline {
+        Baseline {
+            iterations: 10,
+            days: days
+                .iter()
+                .map(|(day, micros)| {
+                    let [parse, part1, part2] = micros.map(|us| us * 1000);
+                    let nanos = PhaseNanos {
+                        parse,
+                        part1,
+                        part2,
+                    };
+                    (*day, nanos)
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        let before = run(&[(1, [100, 1000, 2000]), (5, [1, 50, 80])]);
+        let after = run(&[(1, [100, 1050, 3000]), (5, [3, 50, 80]), (6, [1, 1, 1])]);
+
+        let changes = before.compare(&after);
+        assert_eq!(changes.len(), 6);
+        let regressions = changes
+            .iter()
+            .filter(|change| change.is_regression(0.1))
+            .map(|change| (change.day, change.phase))
+            .collect::<Vec<_>>();
+        // day 1 part 1 is within the threshold, day 5 parse is below the noise floor
+        assert_eq!(regressions, [(1, "part2")]);
+        assert_eq!(changes[2].current, Duration::from_millis(3));
+        assert!((changes[2].relative() - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let baseline = run(&[(1, [100, 1000, 2000]), (12, [4, 5, 6])]);
+        let json = serde_json::to_string(&baseline).unwrap();
+        assert!(json.contains(r#""12":{"parse":4000,"part1":5000,"part2":6000}"#));
+        assert_eq!(serde_json::from_str::<Baseline>(&json).unwrap(), baseline);
+    }
+}
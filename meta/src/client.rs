@@ -0,0 +1,301 @@
+use std::{fmt::Display, time::Duration};
+
+use color_eyre::{
+    eyre::{bail, eyre},
+    Result,
+};
+use secrecy::{ExposeSecret, SecretString};
+
+pub const YEAR: u32 = 2024;
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Talks to the Advent of Code website, or anything serving the same paths under `base_url`.
+pub struct Client {
+    base_url: String,
+    session: SecretString,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    /// `contact` (an email address or repository URL) is appended to the User-Agent so the site
+    /// operators know whom to reach if the runner misbehaves.
+    pub fn new(base_url: &str, session: SecretString, contact: Option<&str>) -> Self {
+        let mut user_agent = format!("{}/{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
+        if let Some(contact) = contact {
+            user_agent.push_str(&format!(" (+{contact})"));
+        }
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(&user_agent)
+            .timeout(Duration::from_secs(30))
+            .redirects(0)
+            .build();
+        Client {
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            session,
+            agent,
+        }
+    }
+
+    /// Downloads the puzzle input of `day`.
+    pub fn input(&self, day: usize) -> Result<String> {
+        let url = format!("{}/{YEAR}/day/{day}/input", self.base_url);
+        let response = self.request("GET", &url).call();
+        Self::read(response, &url, day)
+    }
+
+    /// Posts `answer` for `part` of `day` and interprets the page that comes back.
+    pub fn submit(&self, day: usize, part: u8, answer: &str) -> Result<Submission> {
+        let url = format!("{}/{YEAR}/day/{day}/answer", self.base_url);
+        let response = self
+            .request("POST", &url)
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+        let body = Self::read(response, &url, day)?;
+        let outcome = Outcome::parse(&body)
+            .ok_or_else(|| eyre!("could not make sense of the response from {url}"))?;
+        Ok(Submission {
+            outcome,
+            wait: parse_wait(&body),
+        })
+    }
+
+    fn request(&self, method: &str, url: &str) -> ureq::Request {
+        self.agent.request(method, url).set(
+            "Cookie",
+            &format!("session={}", self.session.expose_secret()),
+        )
+    }
+
+    fn read(
+        response: Result<ureq::Response, ureq::Error>,
+        url: &str,
+        day: usize,
+    ) -> Result<String> {
+        match response {
+            Ok(response) if response.status() == 200 => Ok(response.into_string()?),
+            Ok(response) => bail!(
+                "unexpected response {} {} from {url}, the session token may have expired",
+                response.status(),
+                response.status_text()
+            ),
+            Err(ureq::Error::Status(404, _)) => {
+                bail!("day {day} is not available yet")
+            }
+            Err(ureq::Error::Status(code @ (400 | 401 | 403 | 500), _)) => {
+                bail!("{url} answered {code}, the session token is probably invalid or expired")
+            }
+            Err(ureq::Error::Status(code, response)) => {
+                let body = response.into_string().unwrap_or_default();
+                bail!(
+                    "{url} answered {code}: {}",
+                    body.lines().next().unwrap_or("")
+                )
+            }
+            Err(ureq::Error::Transport(e)) => Err(eyre!("could not reach {url}: {e}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// The previous answer was submitted too recently.
+    Wait,
+    /// The part was already solved, so the answer was not checked.
+    AlreadySolved,
+}
+
+impl Outcome {
+    pub fn parse(body: &str) -> Option<Self> {
+        if body.contains("That's the right answer") {
+            Some(Outcome::Correct)
+        } else if body.contains("That's not the right answer") {
+            if body.contains("your answer is too high") {
+                Some(Outcome::TooHigh)
+            } else if body.contains("your answer is too low") {
+                Some(Outcome::TooLow)
+            } else {
+                Some(Outcome::Wrong)
+            }
+        } else if body.contains("You gave an answer too recently") {
+            Some(Outcome::Wait)
+        } else if body.contains("You don't seem to be solving the right level") {
+            Some(Outcome::AlreadySolved)
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "right answer"),
+            Outcome::Wrong => write!(f, "wrong answer"),
+            Outcome::TooHigh => write!(f, "wrong answer, too high"),
+            Outcome::TooLow => write!(f, "wrong answer, too low"),
+            Outcome::Wait => write!(f, "answered too recently"),
+            Outcome::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Submission {
+    pub outcome: Outcome,
+    /// How long the site asks to wait before the next submission.
+    pub wait: Option<Duration>,
+}
+
+/// Finds `You have 1m 2s left to wait` or `please wait 5 minutes before trying again`.
+fn parse_wait(body: &str) -> Option<Duration> {
+    if let Some((_, rest)) = body.split_once("You have ") {
+        let (left, _) = rest.split_once(" left to wait")?;
+        let mut secs = 0;
+        for part in left.split_whitespace() {
+            let (n, unit) = part.split_at(part.len().checked_sub(1)?);
+            secs += n.parse::<u64>().ok()?
+                * match unit {
+                    "h" => 3600,
+                    "m" => 60,
+                    "s" => 1,
+                    _ => return None,
+                };
+        }
+        return Some(Duration::from_secs(secs));
+    }
+    let body = body.to_lowercase();
+    let (_, rest) = body.split_once("please wait ")?;
+    let (n, rest) = rest.split_once(' ')?;
+    if !rest.starts_with("minute") {
+        return None;
+    }
+    let minutes = match n {
+        "one" => 1,
+        n => n.parse::<u64>().ok()?,
+    };
+    Some(Duration::from_secs(minutes * 60))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::JoinHandle,
+        time::Duration,
+    };
+
+    use secrecy::SecretString;
+
+    use crate::client::{Client, Outcome, Submission};
+
+    /// Serves `response` to a single connection and returns the request it received.
+    fn serve_once(response: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            while reader.read_line(&mut request).unwrap() > 2 {}
+            let len = request
+                .lines()
+                .find_map(|line| line.strip_prefix("Content-Length: "))
+                .map_or(0, |len| len.parse().unwrap());
+            let mut body = vec![0; len];
+            reader.read_exact(&mut body).unwrap();
+            request.push_str(std::str::from_utf8(&body).unwrap());
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            request
+        });
+        (url, handle)
+    }
+
+    fn client(url: &str) -> Client {
+        Client::new(
+            url,
+            SecretString::from("c0ffee".to_owned()),
+            Some("me@example.com"),
+        )
+    }
+
+    #[test]
+    fn test_input() {
+        let (url, server) =
+            serve_once("HTTP/1.1 200 OK\r\nContent-Length: 12\r\n\r\n3   4\n4   3\n");
+        assert_eq!(client(&url).input(1).unwrap(), "3   4\n4   3\n");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2024/day/1/input HTTP/1.1\r\n"));
+        assert!(request.contains("\r\nCookie: session=c0ffee\r\n"));
+        assert!(request.contains(&format!(
+            "\r\nUser-Agent: meta/{} (+me@example.com)\r\n",
+            env!("CARGO_PKG_VERSION")
+        )));
+    }
+
+    #[test]
+    fn test_input_errors() {
+        let (url, server) = serve_once("HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n");
+        let e = client(&url).input(25).unwrap_err();
+        assert_eq!(e.to_string(), "day 25 is not available yet");
+        server.join().unwrap();
+
+        let (url, server) = serve_once("HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\n\r\n");
+        let e = client(&url).input(1).unwrap_err();
+        assert!(e.to_string().contains("session token"));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_submit() {
+        let (url, server) = serve_once(
+            "HTTP/1.1 200 OK\r\nContent-Length: 50\r\n\r\n<article><p>That's the right answer!</p></article>",
+        );
+        let submission = client(&url).submit(1, 2, "31").unwrap();
+        assert_eq!(
+            submission,
+            Submission {
+                outcome: Outcome::Correct,
+                wait: None
+            }
+        );
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2024/day/1/answer HTTP/1.1\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=2&answer=31"));
+    }
+
+    #[test]
+    fn test_outcome() {
+        let parse = |body| (Outcome::parse(body), super::parse_wait(body));
+        assert_eq!(
+            parse("That's not the right answer; your answer is too high.  If you're stuck, \
+                make sure you're using the full input data. Please wait one minute before trying again."),
+            (Some(Outcome::TooHigh), Some(Duration::from_secs(60)))
+        );
+        assert_eq!(
+            parse("That's not the right answer; your answer is too low. please wait 5 minutes before trying again."),
+            (Some(Outcome::TooLow), Some(Duration::from_secs(300)))
+        );
+        assert_eq!(
+            parse("That's not the right answer. If you're stuck, make sure you're using the full input data."),
+            (Some(Outcome::Wrong), None)
+        );
+        assert_eq!(
+            parse(
+                "You gave an answer too recently; you have to wait after submitting an answer \
+                before trying again.  You have 1m 2s left to wait."
+            ),
+            (Some(Outcome::Wait), Some(Duration::from_secs(62)))
+        );
+        assert_eq!(
+            parse("You don't seem to be solving the right level.  Did you already complete it?"),
+            (Some(Outcome::AlreadySolved), None)
+        );
+        assert_eq!(parse("<html>Log in</html>"), (None, None));
+    }
+}
@@ -0,0 +1,231 @@
+use std::{
+    io::{Read, Write},
+    path::Path,
+};
+
+use age::{scrypt, x25519};
+use color_eyre::{
+    eyre::{bail, eyre},
+    Result,
+};
+use secrecy::SecretString;
+
+/// Everything the runner can decrypt with: the scrypt passphrase and/or X25519 keys from identity files.
+#[derive(Default)]
+pub struct Identities(Vec<Box<dyn age::Identity>>);
+
+impl Identities {
+    pub fn add_passphrase(&mut self, passphrase: SecretString) {
+        self.0.push(Box::new(scrypt::Identity::new(passphrase)));
+    }
+
+    /// Adds all identities of an age identity file, as written by `age-keygen` or `meta keygen`.
+    pub fn add_file(&mut self, path: &Path) -> Result<()> {
+        let file = age::IdentityFile::from_file(path.to_string_lossy().into_owned())
+            .map_err(|e| eyre!("{}: {e}", path.display()))?;
+        self.0.extend(file.into_identities()?);
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>> {
+        // passes binary files through unchanged
+        let decryptor = age::Decryptor::new_buffered(age::armor::ArmoredReader::new(ciphertext))?;
+        let mut reader = decryptor.decrypt(self.0.iter().map(|i| i.as_ref()))?;
+        let mut plaintext = vec![];
+        reader.read_to_end(&mut plaintext)?;
+        Ok(plaintext)
+    }
+
+    pub fn decrypt_file(&self, path: &Path) -> Result<String> {
+        let ciphertext = std::fs::read(path)?;
+        Ok(String::from_utf8(self.decrypt(&ciphertext)?)?)
+    }
+}
+
+/// Who an input gets encrypted to: either everyone knowing the passphrase, or a set of X25519 keys.
+pub enum Recipients {
+    Passphrase {
+        passphrase: SecretString,
+        /// scrypt log2(N); age picks one that takes about a second when it is `None`.
+        work_factor: Option<u8>,
+    },
+    Keys(Vec<x25519::Recipient>),
+}
+
+impl Recipients {
+    /// Parses `age1...` public keys, one per line, ignoring blank lines and `#` comments.
+    pub fn parse_keys<'a>(
+        keys: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Vec<x25519::Recipient>> {
+        keys.into_iter()
+            .map(str::trim)
+            .filter(|key| !key.is_empty() && !key.starts_with('#'))
+            .map(|key| {
+                key.parse::<x25519::Recipient>()
+                    .map_err(|e| eyre!("invalid recipient `{key}`: {e}"))
+            })
+            .collect()
+    }
+
+    /// The public keys of the X25519 identities in an age identity file, so that a file can be
+    /// encrypted to the same identity that later decrypts it.
+    pub fn identity_file_keys(path: &Path) -> Result<Vec<x25519::Recipient>> {
+        std::fs::read_to_string(path)?
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                line.parse::<x25519::Identity>()
+                    .map(|identity| identity.to_public())
+                    .map_err(|_| eyre!("{}: not an X25519 identity", path.display()))
+            })
+            .collect()
+    }
+
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            Recipients::Passphrase {
+                passphrase,
+                work_factor,
+            } => {
+                let mut recipient = scrypt::Recipient::new(passphrase.clone());
+                if let Some(log_n) = work_factor {
+                    recipient.set_work_factor(*log_n);
+                }
+                Ok(age::encrypt(&recipient, plaintext)?)
+            }
+            Recipients::Keys(keys) => {
+                if keys.is_empty() {
+                    bail!("no recipients given");
+                }
+                let encryptor = age::Encryptor::with_recipients(
+                    keys.iter().map(|key| key as &dyn age::Recipient),
+                )?;
+                let mut ciphertext = vec![];
+                let mut writer = encryptor.wrap_output(&mut ciphertext)?;
+                writer.write_all(plaintext)?;
+                writer.finish()?;
+                Ok(ciphertext)
+            }
+        }
+    }
+}
+
+/// Whether `data` starts with the header of a binary or ASCII-armored age file.
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(b"age-encryption.org/")
+        || data
+            .trim_ascii_start()
+            .starts_with(b"-----BEGIN AGE ENCRYPTED FILE-----")
+}
+
+/// Writes `data` next to `path` first and renames it into place, so a failed write never
+/// leaves a truncated input behind.
+pub fn write_atomic(path: &Path, data: &[u8]) -> Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    std::fs::write(&tmp, data)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Like [`write_atomic`], but on unix only the owner can read the file, for secret keys.
+pub fn write_private(path: &Path, data: &[u8]) -> Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let mut options = std::fs::OpenOptions::new();
+    // a new file, an existing one would keep its permissions
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options
+        .open(&tmp)
+        .map_err(|e| eyre!("{}: {e}", Path::new(&tmp).display()))?;
+    file.write_all(data)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use age::x25519;
+    use secrecy::SecretString;
+
+    use crate::crypt::{is_encrypted, write_private, Identities, Recipients};
+
+    #[test]
+    fn test_passphrase_roundtrip() {
+        let passphrase = || SecretString::from("hunter2".to_owned());
+        let recipients = Recipients::Passphrase {
+            passphrase: passphrase(),
+            work_factor: Some(10),
+        };
+        let ciphertext = recipients.encrypt(b"3   4\n4   3\n").unwrap();
+        assert!(is_encrypted(&ciphertext));
+        assert!(!is_encrypted(b"3   4\n4   3\n"));
+
+        let mut identities = Identities::default();
+        identities.add_passphrase(passphrase());
+        assert_eq!(identities.decrypt(&ciphertext).unwrap(), b"3   4\n4   3\n");
+
+        let mut wrong = Identities::default();
+        wrong.add_passphrase(SecretString::from("hunter3".to_owned()));
+        assert!(wrong.decrypt(&ciphertext).is_err());
+    }
+
+    #[test]
+    fn test_multiple_recipients() {
+        let alice = x25519::Identity::generate();
+        let bob = x25519::Identity::generate();
+        let eve = x25519::Identity::generate();
+        let recipients = Recipients::Keys(vec![alice.to_public(), bob.to_public()]);
+        let ciphertext = recipients.encrypt(b"2333133121414131402").unwrap();
+
+        for key in [alice, bob] {
+            let identities = Identities(vec![Box::new(key)]);
+            assert_eq!(
+                identities.decrypt(&ciphertext).unwrap(),
+                b"2333133121414131402"
+            );
+        }
+        assert!(Identities(vec![Box::new(eve)])
+            .decrypt(&ciphertext)
+            .is_err());
+    }
+
+    #[test]
+    fn test_armored() {
+        use std::io::Write;
+
+        let key = x25519::Identity::generate();
+        let encryptor =
+            age::Encryptor::with_recipients(std::iter::once(&key.to_public() as _)).unwrap();
+        let mut armored = vec![];
+        let output =
+            age::armor::ArmoredWriter::wrap_output(&mut armored, age::armor::Format::AsciiArmor)
+                .unwrap();
+        let mut writer = encryptor.wrap_output(output).unwrap();
+        writer.write_all(b"125 17\n").unwrap();
+        writer.finish().unwrap().finish().unwrap();
+
+        assert!(is_encrypted(&armored));
+        let identities = Identities(vec![Box::new(key)]);
+        assert_eq!(identities.decrypt(&armored).unwrap(), b"125 17\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = std::env::temp_dir().join(format!("meta-key-{}.txt", std::process::id()));
+        write_private(&path, b"AGE-SECRET-KEY-1\n").unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,60 @@
+use std::path::{Path, PathBuf};
+
+/// The conventional layout of puzzle inputs: `dayNN.txt.age` (or plain `dayNN.txt`) next to
+/// `dayNN.answers.age` in one directory.
+#[derive(Debug, Clone)]
+pub struct InputsDir(PathBuf);
+
+impl InputsDir {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        InputsDir(dir.into())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+
+    /// Where a new encrypted input for `day` should be stored.
+    pub fn input(&self, day: usize) -> PathBuf {
+        self.0.join(format!("day{day:02}.txt.age"))
+    }
+
+    pub fn answers(&self, day: usize) -> PathBuf {
+        self.0.join(format!("day{day:02}.answers.age"))
+    }
+
+    /// Holds the Unix time before which the site asked not to submit another answer.
+    pub fn cooldown(&self) -> PathBuf {
+        self.0.join(".submit-cooldown")
+    }
+
+    /// All file names an input for `day` is looked up under, in order of preference.
+    pub fn candidates(&self, day: usize) -> [PathBuf; 2] {
+        [self.input(day), self.0.join(format!("day{day:02}.txt"))]
+    }
+
+    pub fn find(&self, day: usize) -> Option<PathBuf> {
+        self.candidates(day).into_iter().find(|path| path.is_file())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::inputs::InputsDir;
+
+    #[test]
+    fn test_find() {
+        let dir = std::env::temp_dir().join(format!("meta-inputs-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let inputs = InputsDir::new(&dir);
+
+        assert_eq!(inputs.find(1), None);
+        std::fs::write(dir.join("day01.txt"), "3   4\n").unwrap();
+        assert_eq!(inputs.find(1), Some(dir.join("day01.txt")));
+        std::fs::write(dir.join("day01.txt.age"), "").unwrap();
+        assert_eq!(inputs.find(1), Some(dir.join("day01.txt.age")));
+        assert_eq!(inputs.answers(1), dir.join("day01.answers.age"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,134 @@
+use std::{
+    cell::{Cell, RefCell},
+    fmt::Display,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::{mpsc, Once},
+    thread,
+    time::Duration,
+};
+
+/// Some solvers recurse deeply, so they get as much stack as the main thread usually has.
+const STACK_SIZE: usize = 8 << 20;
+
+thread_local! {
+    static ISOLATED: Cell<bool> = const { Cell::new(false) };
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Why an isolated solver did not return.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    /// It reached `todo!()` or `unimplemented!()`.
+    Unimplemented(String),
+    Crashed(String),
+    TimedOut(Duration),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Unimplemented(message) => write!(f, "unimplemented: {message}"),
+            Failure::Crashed(message) => write!(f, "crashed: {message}"),
+            Failure::TimedOut(limit) => write!(f, "timed out after {limit:?}"),
+        }
+    }
+}
+
+/// Keeps panics of isolated threads off stderr, remembering their message and location instead.
+fn install_hook() {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let default = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if !ISOLATED.with(Cell::get) {
+                return default(info);
+            }
+            let payload = info.payload();
+            let message = payload
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("Box<dyn Any>");
+            let message = match info.location() {
+                Some(location) => format!("{message} at {location}"),
+                None => message.to_owned(),
+            };
+            LAST_PANIC.with(|last| *last.borrow_mut() = Some(message));
+        }));
+    });
+}
+
+/// Runs `f` on its own thread and turns a panic into [`Failure::Unimplemented`] or
+/// [`Failure::Crashed`]. If `f` takes longer than `timeout` it is left running in the background,
+/// there is no way to stop a thread, and [`Failure::TimedOut`] is returned.
+pub fn isolate<T, F>(timeout: Option<Duration>, f: F) -> Result<T, Failure>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    install_hook();
+    let (tx, rx) = mpsc::channel();
+    thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || {
+            ISOLATED.with(|isolated| isolated.set(true));
+            let result = catch_unwind(AssertUnwindSafe(f)).map_err(|_| {
+                let message = LAST_PANIC.with(|last| last.borrow_mut().take());
+                let message = message.unwrap_or_default();
+                if message.starts_with("not yet implemented")
+                    || message.starts_with("not implemented")
+                {
+                    Failure::Unimplemented(message)
+                } else {
+                    Failure::Crashed(message)
+                }
+            });
+            // The receiver is gone if we timed out.
+            let _ = tx.send(result);
+        })
+        .expect("failed to spawn solver thread");
+
+    match timeout {
+        Some(timeout) => match rx.recv_timeout(timeout) {
+            Ok(result) => result,
+            Err(mpsc::RecvTimeoutError::Timeout) => Err(Failure::TimedOut(timeout)),
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                unreachable!("the solver thread always sends")
+            }
+        },
+        None => rx.recv().expect("the solver thread always sends"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::isolate::{isolate, Failure};
+
+    #[test]
+    fn test_isolate() {
+        assert_eq!(isolate(None, || 1 + 1), Ok(2));
+
+        let Err(Failure::Unimplemented(message)) = isolate(None, || -> u32 { todo!() }) else {
+            panic!("expected unimplemented");
+        };
+        assert!(message.starts_with("not yet implemented at meta/src/isolate.rs:"));
+
+        let data = [1, 2];
+        let index = std::hint::black_box(5);
+        let Err(Failure::Crashed(message)) = isolate(None, move || data[index]) else {
+            panic!("expected a crash");
+        };
+        assert!(message.starts_with("index out of bounds: the len is 2 but the index is 5 at "));
+
+        let timeout = Some(Duration::from_millis(50));
+        assert_eq!(
+            isolate(timeout, || loop {
+                std::thread::sleep(Duration::from_millis(10))
+            }),
+            Err::<(), _>(Failure::TimedOut(Duration::from_millis(50)))
+        );
+        assert_eq!(isolate(timeout, || "fast"), Ok("fast"));
+    }
+}
@@ -0,0 +1,76 @@
+use std::{
+    cell::OnceCell,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::Result;
+use meta::crypt::{self, Identities};
+use secrecy::SecretString;
+
+use crate::passphrase;
+
+/// Identities that are only loaded, and the passphrase only prompted for, once an encrypted
+/// file actually has to be read.
+pub struct Keys {
+    passphrase: OnceCell<SecretString>,
+    files: Vec<PathBuf>,
+    identities: OnceCell<Identities>,
+}
+
+impl Keys {
+    pub fn new(passphrase: Option<SecretString>, files: Vec<PathBuf>) -> Self {
+        Keys {
+            passphrase: passphrase.map(OnceCell::from).unwrap_or_default(),
+            files,
+            identities: OnceCell::new(),
+        }
+    }
+
+    pub fn identities(&self) -> Result<&Identities> {
+        if let Some(identities) = self.identities.get() {
+            return Ok(identities);
+        }
+        let mut identities = Identities::default();
+        if let Some(passphrase) = self.passphrase.get() {
+            identities.add_passphrase(passphrase.clone());
+        }
+        for file in self.files.iter() {
+            identities.add_file(file)?;
+        }
+        if identities.is_empty() {
+            let passphrase = passphrase::prompt("Passphrase: ")?;
+            identities.add_passphrase(passphrase.clone());
+            let _ = self.passphrase.set(passphrase);
+        }
+        Ok(self.identities.get_or_init(|| identities))
+    }
+
+    /// The passphrase given or prompted for so far, e.g. to encrypt a file again under the
+    /// passphrase it was read with.
+    pub fn passphrase(&self) -> Option<SecretString> {
+        self.passphrase.get().cloned()
+    }
+
+    /// Reads an input or answers file, `-` meaning stdin. Files starting with the age header are
+    /// decrypted, everything else is taken as plaintext; `plain` skips the detection.
+    pub fn read(&self, path: &Path, plain: bool) -> Result<String> {
+        let data = if path == Path::new("-") {
+            let mut data = vec![];
+            std::io::stdin().read_to_end(&mut data)?;
+            data
+        } else {
+            std::fs::read(path)?
+        };
+        if plain || !crypt::is_encrypted(&data) {
+            return Ok(String::from_utf8(data)?);
+        }
+        Ok(String::from_utf8(self.identities()?.decrypt(&data)?)?)
+    }
+
+    /// Reads a puzzle input like [`Keys::read`] and normalizes line endings and trailing
+    /// whitespace, see [`parsing::normalize`].
+    pub fn read_input(&self, path: &Path, plain: bool) -> Result<String> {
+        Ok(parsing::normalize(&self.read(path, plain)?))
+    }
+}
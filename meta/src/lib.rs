@@ -1,4 +1,19 @@
+use std::{any::TypeId, ops::RangeInclusive};
+
+use alloc::{AllocReport, CountAllocs};
 use aoc_traits::AdventOfCodeSolutions;
+use parsing::{Example, ParseError, TryParse};
+use render::{Frames, Picture, Render, Simulation};
+use timing::{TimePhases, Timings};
+
+pub mod alloc;
+pub mod baseline;
+pub mod client;
+pub mod crypt;
+pub mod inputs;
+pub mod isolate;
+pub mod timing;
+pub mod verify;
 
 pub struct AoC2024;
 
@@ -53,3 +68,180 @@ impl AdventOfCodeSolutions for AoC2024 {
 
     type Day25 = ();
 }
+
+/// Callback that is invoked with the concrete solver type of a day.
+pub trait DayVisitor {
+    type Output;
+
+    fn visit<D: TryParse + Example + 'static>(self, day: usize) -> Self::Output;
+}
+
+macro_rules! visit_days {
+    ($day:expr, $visitor:expr, $($n:literal => $ty:ident),* $(,)?) => {
+        match $day {
+            $($n => visit_impl::<<AoC2024 as AdventOfCodeSolutions>::$ty, _>($n, $visitor),)*
+            _ => None,
+        }
+    };
+}
+
+fn visit_impl<D: TryParse + Example + 'static, V: DayVisitor>(
+    day: usize,
+    visitor: V,
+) -> Option<V::Output> {
+    if TypeId::of::<D>() == TypeId::of::<()>() {
+        None
+    } else {
+        Some(visitor.visit::<D>(day))
+    }
+}
+
+impl AoC2024 {
+    pub const DAYS: RangeInclusive<usize> = 1..=25;
+
+    /// Runs `visitor` with the solver for `day`, or returns `None` if the day is not implemented.
+    pub fn visit_day<V: DayVisitor>(day: usize, visitor: V) -> Option<V::Output> {
+        visit_days!(day, visitor,
+            1 => Day01, 2 => Day02, 3 => Day03, 4 => Day04, 5 => Day05,
+            6 => Day06, 7 => Day07, 8 => Day08, 9 => Day09, 10 => Day10,
+            11 => Day11, 12 => Day12, 13 => Day13, 14 => Day14, 15 => Day15,
+            16 => Day16, 17 => Day17, 18 => Day18, 19 => Day19, 20 => Day20,
+            21 => Day21, 22 => Day22, 23 => Day23, 24 => Day24, 25 => Day25,
+        )
+    }
+
+    pub fn is_implemented(day: usize) -> bool {
+        struct Implemented;
+        impl DayVisitor for Implemented {
+            type Output = ();
+
+            fn visit<D: TryParse + Example + 'static>(self, _day: usize) {}
+        }
+        Self::visit_day(day, Implemented).is_some()
+    }
+
+    /// Only parses the input of `day`, to report where it is malformed.
+    pub fn check(day: usize, input: &str) -> Option<Result<(), ParseError>> {
+        Self::visit_day(day, CheckInput(input))
+    }
+
+    /// Solves both parts of `day` and returns the answers as strings.
+    pub fn answers(day: usize, input: &str) -> Option<Result<Answers, ParseError>> {
+        Self::visit_day(day, SolveAnswers(input))
+    }
+
+    /// Solves only `part` (1 or 2) of `day`.
+    pub fn answer(day: usize, part: u8, input: &str) -> Option<Result<String, ParseError>> {
+        Self::visit_day(day, SolvePart { input, part })
+    }
+
+    /// Solves `day` `iterations` times and reports min, median and max time of each phase.
+    pub fn timed(
+        day: usize,
+        input: &str,
+        iterations: usize,
+    ) -> Option<Result<(Answers, Timings), ParseError>> {
+        Self::visit_day(day, TimePhases { input, iterations })
+    }
+
+    /// Solves `day` once and counts the allocations of each phase, see [`alloc::CountingAllocator`].
+    pub fn allocations(
+        day: usize,
+        input: &str,
+    ) -> Option<Result<(Answers, AllocReport), ParseError>> {
+        Self::visit_day(day, CountAllocs(input))
+    }
+
+    /// Draws `day`'s map with what its solver found, or returns `None` if it is not drawn.
+    pub fn render(day: usize, input: &str) -> Option<Result<Picture, ParseError>> {
+        fn render<D: TryParse + Render>(day: usize, input: &str) -> Result<Picture, ParseError> {
+            let parsed = D::try_parse_input(input).map_err(|e| e.with_day(day))?;
+            Ok(D::render(&parsed))
+        }
+        Some(match day {
+            6 => render::<<AoC2024 as AdventOfCodeSolutions>::Day06>(day, input),
+            12 => render::<<AoC2024 as AdventOfCodeSolutions>::Day12>(day, input),
+            15 => render::<<AoC2024 as AdventOfCodeSolutions>::Day15>(day, input),
+            16 => render::<<AoC2024 as AdventOfCodeSolutions>::Day16>(day, input),
+            18 => render::<<AoC2024 as AdventOfCodeSolutions>::Day18>(day, input),
+            _ => return None,
+        })
+    }
+
+    /// Draws up to `limit` states of `day`'s simulation into `out`, or returns `None` if it
+    /// is not a simulation.
+    pub fn frames(
+        day: usize,
+        input: &str,
+        out: &mut Frames,
+        limit: usize,
+    ) -> Option<color_eyre::Result<()>> {
+        fn frames<D: TryParse + Simulation>(
+            day: usize,
+            input: &str,
+            out: &mut Frames,
+            limit: usize,
+        ) -> color_eyre::Result<()> {
+            let parsed = D::try_parse_input(input).map_err(|e| e.with_day(day))?;
+            for state in D::states(&parsed).take(limit) {
+                out.push(&D::draw(&parsed, &state))?;
+            }
+            Ok(())
+        }
+        Some(match day {
+            6 => frames::<<AoC2024 as AdventOfCodeSolutions>::Day06>(day, input, out, limit),
+            14 => frames::<<AoC2024 as AdventOfCodeSolutions>::Day14>(day, input, out, limit),
+            15 => frames::<<AoC2024 as AdventOfCodeSolutions>::Day15>(day, input, out, limit),
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: String,
+    pub part2: String,
+}
+
+struct CheckInput<'a>(&'a str);
+
+impl DayVisitor for CheckInput<'_> {
+    type Output = Result<(), ParseError>;
+
+    fn visit<D: TryParse + Example + 'static>(self, day: usize) -> Self::Output {
+        D::try_parse_input(self.0).map_err(|e| e.with_day(day))?;
+        Ok(())
+    }
+}
+
+struct SolveAnswers<'a>(&'a str);
+
+impl DayVisitor for SolveAnswers<'_> {
+    type Output = Result<Answers, ParseError>;
+
+    fn visit<D: TryParse + Example + 'static>(self, day: usize) -> Self::Output {
+        let parsed = D::try_parse_input(self.0).map_err(|e| e.with_day(day))?;
+        Ok(Answers {
+            part1: D::solve_part1(&parsed).to_string(),
+            part2: D::solve_part2(&parsed).to_string(),
+        })
+    }
+}
+
+struct SolvePart<'a> {
+    input: &'a str,
+    part: u8,
+}
+
+impl DayVisitor for SolvePart<'_> {
+    type Output = Result<String, ParseError>;
+
+    fn visit<D: TryParse + Example + 'static>(self, day: usize) -> Self::Output {
+        let parsed = D::try_parse_input(self.input).map_err(|e| e.with_day(day))?;
+        Ok(match self.part {
+            1 => D::solve_part1(&parsed).to_string(),
+            2 => D::solve_part2(&parsed).to_string(),
+            part => panic!("invalid part {part}"),
+        })
+    }
+}
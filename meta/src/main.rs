@@ -1,28 +1,1097 @@
-use std::path::PathBuf;
+use std::{
+    fmt::Display,
+    io::Write,
+    num::NonZeroUsize,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
-use aoc_traits::AdventOfCodeSolutions;
-use clap::Parser;
-use color_eyre::Result;
-use secrecy::SecretString;
+use clap::{
+    parser::ValueSource, ArgGroup, Args, CommandFactory, FromArgMatches, Parser, Subcommand,
+};
+use color_eyre::{
+    eyre::{bail, eyre},
+    Result,
+};
+use keys::Keys;
+use meta::{
+    alloc::{AllocReport, CountingAllocator},
+    baseline::{self, Baseline, PhaseNanos},
+    client::{self, Client, Outcome},
+    crypt::{self, write_atomic, write_private, Recipients},
+    inputs::InputsDir,
+    isolate::{isolate, Failure},
+    timing::Timings,
+    verify::{Expected, Verdict},
+    Answers, AoC2024,
+};
+use parsing::ParseError;
+use render::Frames;
+use report::Format;
+use secrecy::{ExposeSecret, SecretString};
+use table::print_table;
+
+#[global_allocator]
+static ALLOC: CountingAllocator = CountingAllocator;
+
+mod keys;
+mod passphrase;
+mod pool;
+mod report;
+mod table;
 
 #[derive(Parser)]
+#[clap(subcommand_negates_reqs = true)]
+#[clap(group(ArgGroup::new("select").required(true).args(["day", "all", "days"])))]
 struct AoCRunner {
+    #[clap(subcommand)]
+    command: Option<Command>,
+    /// Day to run, with its input looked up in --inputs-dir unless --input is given
     #[clap(short, long)]
-    day: usize,
+    day: Option<usize>,
+    /// Input file, encrypted or plain, or `-` for stdin
     #[clap(short, long)]
-    input: PathBuf,
-    #[clap(short, long, env = "AGE_PASSPHRASE")]
-    passphrase: SecretString,
+    input: Option<PathBuf>,
+    /// Treat the input as plaintext instead of detecting the age header
+    #[clap(long)]
+    plain: bool,
+    /// Run every implemented day
+    #[clap(long)]
+    all: bool,
+    /// Run a range of days, e.g. `5-12`
+    #[clap(long, value_parser = parse_days)]
+    days: Option<RangeInclusive<usize>>,
+    /// Directory containing the `dayNN.txt.age` (or plain `dayNN.txt`) inputs and `dayNN.answers.age` answers
+    #[clap(long, env = "AOC_INPUTS_DIR", default_value = "inputs", global = true)]
+    inputs_dir: PathBuf,
+    /// Time parse, part 1 and part 2 separately
+    #[clap(long)]
+    time: bool,
+    /// Number of timed iterations, reported as min / median / max
+    #[clap(long, default_value_t = 1, requires = "time")]
+    iterations: usize,
+    /// Count the allocations, bytes allocated and peak live bytes of parse, part 1 and part 2
+    #[clap(long)]
+    alloc_stats: bool,
+    /// Give up on a day after this many seconds, 0 for no limit. A day that crashes or times out
+    /// is run again with both parts side by side, each under the same limit, so a hanging day
+    /// takes up to twice this long
+    #[clap(long, default_value_t = 60.0, value_parser = parse_timeout)]
+    timeout: f64,
+    /// Solve up to this many days at the same time, results are still printed in day order.
+    /// Timings get noisier with more than one job.
+    #[clap(short, long, default_value = "1")]
+    jobs: NonZeroUsize,
+    /// Draw the day's map with what the solver found instead of printing the answers, to a
+    /// `.png` or `.ppm` image or `-` for colored text on the terminal
+    #[clap(long, requires = "day")]
+    render: Option<PathBuf>,
+    /// Write a frame per step of the day's simulation instead of printing the answers, as
+    /// numbered PNG images into a directory or as an animated GIF if it ends in `.gif`
+    #[clap(long, requires = "day", conflicts_with = "render")]
+    frames: Option<PathBuf>,
+    /// Stop after this many frames, a simulation can run for a long time or forever
+    #[clap(long, default_value_t = 10_000, requires = "frames")]
+    max_frames: usize,
+    /// Width and height in pixels of a tile of a rendered image or frame
+    #[clap(long, default_value_t = 4)]
+    scale: usize,
+    /// How to print the per-day results
+    #[clap(long, value_enum, default_value_t = Format::Table)]
+    format: Format,
+    /// Deprecated: ends up in shell history and the process list, use AGE_PASSPHRASE,
+    /// --passphrase-file, --passphrase-fd or the interactive prompt instead
+    #[clap(
+        short,
+        long,
+        env = "AGE_PASSPHRASE",
+        hide_env_values = true,
+        global = true
+    )]
+    passphrase: Option<SecretString>,
+    /// Read the passphrase from the first line of a file
+    #[clap(long, global = true)]
+    passphrase_file: Option<PathBuf>,
+    /// Read the passphrase from an inherited file descriptor
+    #[cfg(unix)]
+    #[clap(long, global = true, conflicts_with = "passphrase_file")]
+    passphrase_fd: Option<i32>,
+    /// age identity file with X25519 keys, can be given multiple times
+    #[clap(long = "identity", global = true)]
+    identities: Vec<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Encrypt a plaintext puzzle input with the passphrase or to X25519 recipients
+    Encrypt {
+        plain: PathBuf,
+        out: PathBuf,
+        #[clap(flatten)]
+        recipients: RecipientArgs,
+        /// Overwrite `out` if it already exists
+        #[clap(short, long)]
+        force: bool,
+    },
+    /// Decrypt an input and write it to stdout or `--output`
+    Decrypt {
+        input: PathBuf,
+        #[clap(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Re-encrypt an input in place under a new passphrase or new recipients
+    Rekey {
+        input: PathBuf,
+        /// Prompted for when neither this nor a recipient is given
+        #[clap(long, env = "AGE_NEW_PASSPHRASE", hide_env_values = true)]
+        new_passphrase: Option<SecretString>,
+        #[clap(flatten)]
+        recipients: RecipientArgs,
+    },
+    /// Generate a new X25519 identity file and print its public key
+    Keygen { out: PathBuf },
+    /// Download a puzzle input and store it encrypted in the inputs directory
+    Fetch {
+        #[clap(short, long)]
+        day: usize,
+        /// Download again even if the inputs directory already has the input
+        #[clap(short, long)]
+        force: bool,
+        #[clap(flatten)]
+        server: ServerArgs,
+        #[clap(flatten)]
+        recipients: RecipientArgs,
+    },
+    /// Solve one part of a day and submit the answer, recording it in the answers file if accepted
+    Submit {
+        #[clap(short, long)]
+        day: usize,
+        #[clap(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        #[clap(flatten)]
+        server: ServerArgs,
+        /// Who the updated answers file is encrypted to
+        #[clap(flatten)]
+        recipients: RecipientArgs,
+    },
+    /// Time every day that has an input and compare against the timings of a saved baseline.
+    /// Exits with status 2 if any phase got slower than --threshold.
+    Bench {
+        /// Only benchmark a range of days, e.g. `5-12`
+        #[clap(long, value_parser = parse_days)]
+        days: Option<RangeInclusive<usize>>,
+        /// Number of timed iterations, the median of which is recorded
+        #[clap(long, default_value_t = 10)]
+        iterations: usize,
+        /// Baseline file, `bench-baseline.json` in the inputs directory by default
+        #[clap(long)]
+        baseline: Option<PathBuf>,
+        /// Slowdown in percent above which a phase counts as a regression
+        #[clap(long, default_value_t = 10.0)]
+        threshold: f64,
+        /// Record this run as the new baseline, even if it regressed
+        #[clap(long)]
+        save: bool,
+    },
+    /// Inspect the inputs directory
+    Inputs {
+        #[clap(subcommand)]
+        command: InputsCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum InputsCommand {
+    /// List which days have an input and answers, which are missing and which fail to decrypt
+    Status,
+}
+
+#[derive(Args)]
+struct RecipientArgs {
+    /// X25519 public key (`age1...`) to encrypt to, can be given multiple times
+    #[clap(short = 'r', long = "recipient")]
+    recipients: Vec<String>,
+    /// File with one X25519 public key per line
+    #[clap(short = 'R', long = "recipients-file")]
+    recipients_files: Vec<PathBuf>,
+    /// Also encrypt to the public keys of the `--identity` files
+    #[clap(long)]
+    to_identity: bool,
+    /// scrypt work factor (log2 N) when encrypting with a passphrase
+    #[clap(long)]
+    work_factor: Option<u8>,
+}
+
+impl RecipientArgs {
+    /// Without any recipients, encrypts to the passphrase.
+    fn resolve(
+        self,
+        passphrase: Option<SecretString>,
+        identity_files: &[PathBuf],
+        prompt: &str,
+    ) -> Result<Recipients> {
+        let mut keys = Recipients::parse_keys(self.recipients.iter().map(String::as_str))?;
+        for file in self.recipients_files.iter() {
+            keys.extend(Recipients::parse_keys(
+                std::fs::read_to_string(file)?.lines(),
+            )?);
+        }
+        if self.to_identity {
+            if identity_files.is_empty() {
+                bail!("--to-identity needs an --identity file");
+            }
+            for file in identity_files {
+                keys.extend(Recipients::identity_file_keys(file)?);
+            }
+        }
+        if !keys.is_empty() || !self.recipients_files.is_empty() {
+            return Ok(Recipients::Keys(keys));
+        }
+        let passphrase = match passphrase {
+            Some(passphrase) => passphrase,
+            None => passphrase::prompt_new(prompt)?,
+        };
+        Ok(Recipients::Passphrase {
+            passphrase,
+            work_factor: self.work_factor,
+        })
+    }
+}
+
+#[derive(Args)]
+struct ServerArgs {
+    /// Base URL of the Advent of Code website
+    #[clap(long, env = "AOC_BASE_URL", default_value = client::DEFAULT_BASE_URL)]
+    base_url: String,
+    /// Value of the `session` cookie of a logged in browser
+    #[clap(long, env = "AOC_SESSION", hide_env_values = true)]
+    session: Option<SecretString>,
+    /// Read the session cookie from the first line of a file
+    #[clap(long)]
+    session_file: Option<PathBuf>,
+    /// Email address or repository URL added to the User-Agent
+    #[clap(long, env = "AOC_CONTACT")]
+    contact: Option<String>,
+}
+
+impl ServerArgs {
+    fn client(self) -> Result<Client> {
+        let session = match (self.session_file, self.session) {
+            (Some(path), _) => {
+                let contents =
+                    std::fs::read_to_string(&path).map_err(|e| eyre!("{}: {e}", path.display()))?;
+                SecretString::from(
+                    contents
+                        .lines()
+                        .next()
+                        .unwrap_or_default()
+                        .trim()
+                        .to_owned(),
+                )
+            }
+            (None, Some(session)) => session,
+            (None, None) => bail!("no session token, set AOC_SESSION or pass --session-file"),
+        };
+        if session.expose_secret().is_empty() {
+            bail!("the session token is empty");
+        }
+        Ok(Client::new(
+            &self.base_url,
+            session,
+            self.contact.as_deref(),
+        ))
+    }
+}
+
+fn parse_timeout(s: &str) -> Result<f64, String> {
+    let secs = s.trim().parse::<f64>().map_err(|e| format!("`{s}`: {e}"))?;
+    match Duration::try_from_secs_f64(secs) {
+        Ok(_) => Ok(secs),
+        Err(_) => Err(format!(
+            "`{s}` is not a number of seconds, use 0 for no limit"
+        )),
+    }
+}
+
+fn parse_days(s: &str) -> Result<RangeInclusive<usize>, String> {
+    let parse = |d: &str| {
+        d.trim()
+            .parse::<usize>()
+            .ok()
+            .filter(|d| AoC2024::DAYS.contains(d))
+            .ok_or_else(|| format!("invalid day `{d}`"))
+    };
+    let range = match s.split_once('-') {
+        Some((start, end)) => parse(start)?..=parse(end)?,
+        None => parse(s)?..=parse(s)?,
+    };
+    if range.is_empty() {
+        return Err(format!("empty day range `{s}`"));
+    }
+    Ok(range)
+}
+
+/// A part's answer, or why its solver did not return one.
+type Part = Result<String, Failure>;
+
+enum Status {
+    /// The solvers ran, a part that panicked or ran out of time keeps the other part's answer.
+    Ran {
+        answers: (Part, Part),
+        verdict: (Verdict, Verdict),
+        timings: Option<Box<Timings>>,
+        allocations: Option<AllocReport>,
+    },
+    Unimplemented,
+    MissingInput,
+    InvalidInput(ParseError),
+    Failed(String),
+}
+
+impl Status {
+    /// The parts that did not return an answer.
+    fn failures(&self) -> Vec<(u8, &Failure)> {
+        match self {
+            Status::Ran {
+                answers: (part1, part2),
+                ..
+            } => [(1, part1), (2, part2)]
+                .into_iter()
+                .filter_map(|(part, answer)| Some((part, answer.as_ref().err()?)))
+                .collect(),
+            _ => vec![],
+        }
+    }
+}
+
+/// How a part that did not return shows in the status column.
+fn failure_status(failure: &Failure) -> String {
+    match failure {
+        Failure::Unimplemented(_) => "unimplemented".to_owned(),
+        Failure::Crashed(_) => "crashed".to_owned(),
+        failure => failure.to_string(),
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Ran { .. } => {
+                let failures = self.failures();
+                let statuses = failures
+                    .iter()
+                    .map(|(_, failure)| failure_status(failure))
+                    .collect::<Vec<_>>();
+                match statuses.as_slice() {
+                    [] => write!(f, "ok"),
+                    // both parts failed the same way, e.g. already while parsing
+                    [first, second] if first == second => write!(f, "{first}"),
+                    _ => {
+                        let parts = failures
+                            .iter()
+                            .zip(&statuses)
+                            .map(|((part, _), status)| format!("part {part} {status}"));
+                        write!(f, "{}", parts.collect::<Vec<_>>().join(", "))
+                    }
+                }
+            }
+            Status::Unimplemented => write!(f, "unimplemented"),
+            Status::MissingInput => write!(f, "missing input"),
+            Status::InvalidInput(e) => {
+                write!(f, "invalid input (line {}, column {})", e.line, e.column)
+            }
+            Status::Failed(e) => write!(f, "error: {e}"),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct RunOptions {
+    iterations: Option<usize>,
+    alloc_stats: bool,
+    timeout: Option<Duration>,
+}
+
+/// A day ready to be solved on any thread, or how it ended before getting there. Inputs are
+/// read up front because decrypting them may prompt for the passphrase.
+enum Loaded {
+    Ready { input: String, expected: Expected },
+    Done(Box<Status>),
+}
+
+fn load_day(
+    keys: &Keys,
+    plain: bool,
+    day: usize,
+    input: Option<&Path>,
+    answers: Option<&Path>,
+) -> Loaded {
+    if !AoC2024::is_implemented(day) {
+        return Loaded::Done(Box::new(Status::Unimplemented));
+    }
+    let Some(input) = input else {
+        return Loaded::Done(Box::new(Status::MissingInput));
+    };
+    let input = match keys.read_input(input, plain) {
+        Ok(input) => input,
+        Err(e) => return Loaded::Done(Box::new(Status::Failed(e.to_string()))),
+    };
+    let expected = answers.map_or(Ok(Expected::default()), |answers| {
+        load_expected(keys, answers)
+    });
+    match expected {
+        Ok(expected) => Loaded::Ready { input, expected },
+        Err(e) => Loaded::Done(Box::new(Status::Failed(format!("answers: {e}")))),
+    }
+}
+
+type Solved = (Answers, Option<Timings>, Option<AllocReport>);
+
+fn solve(opts: RunOptions, day: usize, input: &str) -> Result<Solved, ParseError> {
+    let (answers, timings) = match opts.iterations {
+        Some(iterations) => AoC2024::timed(day, input, iterations)
+            .expect("day is implemented")
+            .map(|(answers, timings)| (answers, Some(timings)))?,
+        None => (
+            AoC2024::answers(day, input).expect("day is implemented")?,
+            None,
+        ),
+    };
+    let allocations = opts.alloc_stats.then(|| {
+        let (_, allocations) = AoC2024::allocations(day, input)
+            .expect("day is implemented")
+            .expect("input parsed before");
+        allocations
+    });
+    Ok((answers, timings, allocations))
+}
+
+/// Solves `day` on its own thread, so a panic or an endless loop only takes down this day. If it
+/// does not return, both parts run again at the same time on threads of their own, so that e.g. a
+/// `todo!()` in part 2 still reports the answer of part 1.
+fn run_day(opts: RunOptions, day: usize, input: String, expected: &Expected) -> Status {
+    let whole = {
+        let input = input.clone();
+        isolate(opts.timeout, move || solve(opts, day, &input))
+    };
+    let (answers, timings, allocations) = match whole {
+        Ok(Ok((answers, timings, allocations))) => {
+            let timings = timings.map(Box::new);
+            ((Ok(answers.part1), Ok(answers.part2)), timings, allocations)
+        }
+        Ok(Err(e)) => return Status::InvalidInput(e),
+        Err(_) => {
+            let part = |part| {
+                let input = input.clone();
+                isolate(opts.timeout, move || {
+                    AoC2024::answer(day, part, &input).expect("day is implemented")
+                })
+            };
+            let parts = thread::scope(|s| {
+                let part1 = s.spawn(|| part(1));
+                let part2 = s.spawn(|| part(2));
+                // isolate catches the panics
+                (part1.join().unwrap(), part2.join().unwrap())
+            });
+            match parts {
+                (Ok(Err(e)), _) | (_, Ok(Err(e))) => return Status::InvalidInput(e),
+                (part1, part2) => {
+                    let answer = |part: Result<Result<String, ParseError>, Failure>| {
+                        part.map(|answer| answer.expect("parse errors are returned above"))
+                    };
+                    ((answer(part1), answer(part2)), None, None)
+                }
+            }
+        }
+    };
+    let verdict = |part, answer: &Part| match answer {
+        Ok(answer) => expected.check_part(part, answer),
+        Err(_) => Verdict::Unknown,
+    };
+    Status::Ran {
+        verdict: (verdict(1, &answers.0), verdict(2, &answers.1)),
+        answers,
+        timings,
+        allocations,
+    }
+}
+
+fn load_expected(keys: &Keys, path: &Path) -> Result<Expected> {
+    if !path.exists() {
+        return Ok(Expected::default());
+    }
+    keys.read(path, false)?
+        .parse::<Expected>()
+        .map_err(|e| eyre!(e))
+}
+
+fn check_cell(status: &Status) -> String {
+    match status {
+        Status::Ran {
+            verdict: (part1, part2),
+            ..
+        } => match part1.combine(*part2) {
+            Verdict::Fail => {
+                let failed = [(1, part1), (2, part2)]
+                    .into_iter()
+                    .filter(|(_, v)| **v == Verdict::Fail)
+                    .map(|(part, _)| format!("part {part}"))
+                    .collect::<Vec<_>>();
+                format!("fail ({})", failed.join(", "))
+            }
+            verdict => verdict.to_string(),
+        },
+        _ => "-".to_owned(),
+    }
+}
+
+fn print_summary(results: &[(usize, Status)]) {
+    let rows = results
+        .iter()
+        .map(|(day, status)| {
+            let (part1, part2) = match status {
+                Status::Ran {
+                    answers: (part1, part2),
+                    ..
+                } => (
+                    part1.as_deref().unwrap_or("-").to_owned(),
+                    part2.as_deref().unwrap_or("-").to_owned(),
+                ),
+                _ => ("-".to_owned(), "-".to_owned()),
+            };
+            [
+                day.to_string(),
+                part1,
+                part2,
+                status.to_string(),
+                check_cell(status),
+            ]
+        })
+        .collect::<Vec<_>>();
+    print_table(["Day", "Part 1", "Part 2", "Status", "Check"], &rows);
+}
+
+fn print_timings(results: &[(usize, Status)]) {
+    let rows = results
+        .iter()
+        .filter_map(|(day, status)| match status {
+            Status::Ran {
+                timings: Some(timings),
+                ..
+            } => Some([
+                day.to_string(),
+                timings.parse.to_string(),
+                timings.part1.to_string(),
+                timings.part2.to_string(),
+            ]),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    print_table(["Day", "Parse", "Part 1", "Part 2"], &rows);
+}
+
+fn print_allocations(results: &[(usize, Status)]) {
+    let rows = results
+        .iter()
+        .filter_map(|(day, status)| match status {
+            Status::Ran {
+                allocations: Some(allocations),
+                ..
+            } => Some((day, allocations)),
+            _ => None,
+        })
+        .flat_map(|(day, report)| {
+            [
+                ("parse", report.parse),
+                ("part1", report.part1),
+                ("part2", report.part2),
+            ]
+            .map(|(phase, stats)| {
+                [
+                    day.to_string(),
+                    phase.to_owned(),
+                    stats.allocations.to_string(),
+                    stats.bytes.to_string(),
+                    stats.peak.to_string(),
+                ]
+            })
+        })
+        .collect::<Vec<_>>();
+    print_table(
+        ["Day", "Phase", "Allocations", "Bytes", "Peak bytes"],
+        &rows,
+    );
+}
+
+fn inputs_status(keys: &Keys, inputs: &InputsDir) {
+    let check = |path: &Path| match std::fs::read(path) {
+        Ok(data) if crypt::is_encrypted(&data) => match keys.read(path, false) {
+            Ok(_) => "ok".to_owned(),
+            Err(e) => format!("fails to decrypt: {e}"),
+        },
+        Ok(_) if path.extension().is_some_and(|ext| ext == "age") => {
+            "fails to decrypt: not an age file".to_owned()
+        }
+        Ok(_) => "ok (plain)".to_owned(),
+        Err(e) => format!("unreadable: {e}"),
+    };
+
+    let rows = AoC2024::DAYS
+        .filter_map(|day| {
+            let input = inputs.find(day);
+            let answers = inputs.answers(day);
+            if !AoC2024::is_implemented(day) && input.is_none() {
+                return None;
+            }
+            let (file, input) = match input {
+                Some(path) => (path.display().to_string(), check(&path)),
+                None => ("-".to_owned(), "missing".to_owned()),
+            };
+            let answers = if answers.is_file() {
+                check(&answers)
+            } else {
+                "missing".to_owned()
+            };
+            Some([day.to_string(), file, input, answers])
+        })
+        .collect::<Vec<_>>();
+    println!("{}", inputs.path().display());
+    print_table(["Day", "File", "Input", "Answers"], &rows);
+}
+
+fn submit(
+    keys: &Keys,
+    inputs: &InputsDir,
+    day: usize,
+    part: u8,
+    server: ServerArgs,
+    recipients: impl FnOnce() -> Result<Recipients>,
+) -> Result<()> {
+    let Some(input) = inputs.find(day) else {
+        bail!("no input for day {day} in {}", inputs.path().display());
+    };
+    let input = keys.read_input(&input, false)?;
+    let answer = AoC2024::answer(day, part, &input)
+        .ok_or_else(|| eyre!("day {day} is not implemented"))??;
+    println!("day {day} part {part}: {answer}");
+
+    let answers = inputs.answers(day);
+    let mut expected = load_expected(keys, &answers)?;
+    match expected.part_mut(part) {
+        Some(accepted) if *accepted == answer => {
+            println!("already accepted");
+            return Ok(());
+        }
+        Some(accepted) => bail!("`{accepted}` was already accepted for this part, not submitting"),
+        None => {}
+    }
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let cooldown = inputs.cooldown();
+    if let Ok(until) = std::fs::read_to_string(&cooldown) {
+        let until = until.trim().parse::<u64>().unwrap_or_default();
+        if now < until {
+            bail!("answered too recently, wait another {}s", until - now);
+        }
+    }
+
+    let submission = server.client()?.submit(day, part, &answer)?;
+    if let Some(wait) = submission.wait {
+        std::fs::write(&cooldown, (now + wait.as_secs()).to_string())?;
+    }
+    match submission.outcome {
+        Outcome::Correct => {
+            println!("{}", submission.outcome);
+            *expected.part_mut(part) = Some(answer);
+            let recipients = recipients()?;
+            write_atomic(
+                &answers,
+                &recipients.encrypt(expected.to_string().as_bytes())?,
+            )?;
+            Ok(())
+        }
+        outcome => Err(eyre!("day {day} part {part}: {outcome}")),
+    }
+}
+
+struct BenchOptions {
+    days: Option<RangeInclusive<usize>>,
+    iterations: usize,
+    baseline: Option<PathBuf>,
+    threshold: f64,
+    save: bool,
+}
+
+/// Returns the number of regressed phases.
+fn bench(keys: &Keys, inputs: &InputsDir, opts: BenchOptions) -> Result<usize> {
+    let days = opts.days.unwrap_or(AoC2024::DAYS);
+    let mut current = Baseline {
+        iterations: opts.iterations,
+        ..Baseline::default()
+    };
+    for day in days.filter(|day| AoC2024::is_implemented(*day)) {
+        let Some(path) = inputs.find(day) else {
+            continue;
+        };
+        let input = keys.read_input(&path, false)?;
+        let (_, timings) =
+            AoC2024::timed(day, &input, opts.iterations).expect("day is implemented")?;
+        current.days.insert(day, PhaseNanos::from(&timings));
+    }
+    if current.days.is_empty() {
+        bail!("no inputs to benchmark in {}", inputs.path().display());
+    }
+
+    let path = opts
+        .baseline
+        .unwrap_or_else(|| inputs.path().join("bench-baseline.json"));
+    let previous = if path.exists() {
+        Some(Baseline::load(&path)?)
+    } else {
+        None
+    };
+    let threshold = opts.threshold / 100.0;
+    let changes = previous
+        .as_ref()
+        .map(|previous| previous.compare(&current))
+        .unwrap_or_default();
+    let regressions = changes
+        .iter()
+        .filter(|change| change.is_regression(threshold))
+        .count();
+    let mut rows = vec![];
+    for (day, now) in current.days.iter() {
+        let compared = changes
+            .iter()
+            .filter(|change| change.day == *day)
+            .collect::<Vec<_>>();
+        // a day new since the baseline only has its current times
+        if compared.is_empty() {
+            for (phase, current) in baseline::PHASES.into_iter().zip(now.phases()) {
+                rows.push([
+                    day.to_string(),
+                    phase.to_owned(),
+                    "-".to_owned(),
+                    format!("{current:?}"),
+                    "-".to_owned(),
+                    String::new(),
+                ]);
+            }
+        }
+        for change in compared {
+            let regressed = change.is_regression(threshold);
+            rows.push([
+                day.to_string(),
+                change.phase.to_owned(),
+                format!("{:?}", change.baseline),
+                format!("{:?}", change.current),
+                format!("{:+.1}%", change.relative() * 100.0),
+                if regressed { "slower" } else { "" }.to_owned(),
+            ]);
+        }
+    }
+    print_table(["Day", "Phase", "Baseline", "Current", "Change", ""], &rows);
+
+    if previous.is_none() || opts.save {
+        current.save(&path)?;
+        eprintln!("saved baseline to {}", path.display());
+    }
+    Ok(regressions)
+}
+
+fn run_command(
+    command: Command,
+    passphrase: Option<SecretString>,
+    identity_files: Vec<PathBuf>,
+    inputs: &InputsDir,
+) -> Result<()> {
+    match command {
+        Command::Encrypt {
+            plain,
+            out,
+            recipients,
+            force,
+        } => {
+            if out.exists() && !force {
+                bail!(
+                    "{} already exists, pass --force to overwrite",
+                    out.display()
+                );
+            }
+            let recipients = recipients.resolve(passphrase, &identity_files, "Passphrase: ")?;
+            let plaintext = std::fs::read(&plain)?;
+            write_atomic(&out, &recipients.encrypt(&plaintext)?)?;
+        }
+        Command::Decrypt { input, output } => {
+            let keys = Keys::new(passphrase, identity_files);
+            let plaintext = keys.identities()?.decrypt(&std::fs::read(&input)?)?;
+            match output {
+                Some(output) => std::fs::write(output, plaintext)?,
+                None => std::io::stdout().write_all(&plaintext)?,
+            }
+        }
+        Command::Rekey {
+            input,
+            new_passphrase,
+            recipients,
+        } => {
+            let keys = Keys::new(passphrase, identity_files.clone());
+            let plaintext = keys.identities()?.decrypt(&std::fs::read(&input)?)?;
+            let recipients =
+                recipients.resolve(new_passphrase, &identity_files, "New passphrase: ")?;
+            write_atomic(&input, &recipients.encrypt(&plaintext)?)?;
+        }
+        Command::Keygen { out } => {
+            if out.exists() {
+                bail!("{} already exists", out.display());
+            }
+            let identity = age::x25519::Identity::generate();
+            let public = identity.to_public();
+            let file = format!(
+                "# public key: {public}\n{}\n",
+                identity.to_string().expose_secret()
+            );
+            write_private(&out, file.as_bytes())?;
+            println!("{public}");
+        }
+        Command::Fetch {
+            day,
+            force,
+            server,
+            recipients,
+        } => {
+            if !AoC2024::DAYS.contains(&day) {
+                bail!("invalid day {day}");
+            }
+            if let Some(cached) = inputs.find(day).filter(|_| !force) {
+                eprintln!("day {day}: already have {}", cached.display());
+                return Ok(());
+            }
+            let recipients = recipients.resolve(passphrase, &identity_files, "Passphrase: ")?;
+            let input = server.client()?.input(day)?;
+            let out = inputs.input(day);
+            std::fs::create_dir_all(inputs.path())?;
+            write_atomic(&out, &recipients.encrypt(input.as_bytes())?)?;
+            eprintln!("day {day}: saved {}", out.display());
+        }
+        Command::Submit {
+            day,
+            part,
+            server,
+            recipients,
+        } => {
+            let keys = Keys::new(passphrase, identity_files.clone());
+            submit(&keys, inputs, day, part, server, || {
+                // the input was just decrypted, so a prompted passphrase is not asked for again
+                recipients.resolve(keys.passphrase(), &identity_files, "Passphrase: ")
+            })?;
+        }
+        Command::Bench {
+            days,
+            iterations,
+            baseline,
+            threshold,
+            save,
+        } => {
+            let keys = Keys::new(passphrase, identity_files);
+            let opts = BenchOptions {
+                days,
+                iterations,
+                baseline,
+                threshold,
+                save,
+            };
+            let regressions = bench(&keys, inputs, opts)?;
+            if regressions > 0 {
+                eprintln!("{regressions} phase(s) got more than {threshold}% slower");
+                std::process::exit(2);
+            }
+        }
+        Command::Inputs {
+            command: InputsCommand::Status,
+        } => inputs_status(&Keys::new(passphrase, identity_files), inputs),
+    }
+    Ok(())
+}
+
+impl AoCRunner {
+    /// Resolves the passphrase from, in order, `--passphrase-file`, `--passphrase-fd` and
+    /// `--passphrase`/`AGE_PASSPHRASE`. `None` means the caller prompts for it if it needs one.
+    fn take_passphrase(&mut self) -> Result<Option<SecretString>> {
+        if let Some(path) = &self.passphrase_file {
+            return passphrase::read_file(path).map(Some);
+        }
+        #[cfg(unix)]
+        if let Some(fd) = self.passphrase_fd {
+            return passphrase::read_fd(fd).map(Some);
+        }
+        Ok(self.passphrase.take())
+    }
 }
 
 fn main() -> Result<()> {
-    let args = AoCRunner::parse();
+    let matches = AoCRunner::command().get_matches();
+    let mut args = AoCRunner::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    if matches.value_source("passphrase") == Some(ValueSource::CommandLine) {
+        eprintln!(
+            "warning: --passphrase is deprecated, use AGE_PASSPHRASE, --passphrase-file, --passphrase-fd or the prompt"
+        );
+    }
 
-    let identity = age::scrypt::Identity::new(args.passphrase);
-    let enc_input = std::fs::read(&args.input)?;
-    let input = String::from_utf8(age::decrypt(&identity, &enc_input)?)?;
+    let passphrase = args.take_passphrase()?;
+    let inputs = InputsDir::new(&args.inputs_dir);
 
-    meta::AoC2024::solve_day(args.day, &input).map_err(|e| color_eyre::eyre::eyre!(e))?;
+    if let Some(command) = args.command.take() {
+        return run_command(command, passphrase, args.identities, &inputs);
+    }
+
+    let keys = Keys::new(passphrase, args.identities);
+    let opts = RunOptions {
+        iterations: args.time.then_some(args.iterations),
+        alloc_stats: args.alloc_stats,
+        timeout: (args.timeout > 0.0).then(|| Duration::from_secs_f64(args.timeout)),
+    };
+
+    let loaded = match args.day {
+        Some(day) => {
+            let (input, answers) = match args.input {
+                Some(input) if input == Path::new("-") => (input, None),
+                Some(input) => {
+                    let dir = InputsDir::new(input.parent().unwrap_or(Path::new(".")));
+                    (input, Some(dir.answers(day)))
+                }
+                None => match inputs.find(day) {
+                    Some(input) => (input, Some(inputs.answers(day))),
+                    None => bail!(
+                        "no input for day {day}, expected {} or pass --input",
+                        inputs.input(day).display()
+                    ),
+                },
+            };
+            if let Some(out) = &args.render {
+                let input = keys.read_input(&input, args.plain)?;
+                let picture = AoC2024::render(day, &input)
+                    .ok_or_else(|| eyre!("day {day} has nothing to render"))??;
+                if out == Path::new("-") {
+                    print!("{}", picture.ansi());
+                } else {
+                    picture.save(out, args.scale)?;
+                }
+                return Ok(());
+            }
+            if let Some(out) = &args.frames {
+                let input = keys.read_input(&input, args.plain)?;
+                let mut frames =
+                    Frames::create(out, args.scale).map_err(|e| eyre!("{}: {e}", out.display()))?;
+                AoC2024::frames(day, &input, &mut frames, args.max_frames)
+                    .ok_or_else(|| eyre!("day {day} is not a simulation"))??;
+                let count = frames.count();
+                frames.finish()?;
+                eprintln!("day {day}: wrote {count} frames to {}", out.display());
+                return Ok(());
+            }
+            vec![(
+                day,
+                load_day(&keys, args.plain, day, Some(&input), answers.as_deref()),
+            )]
+        }
+        None => {
+            let days = match args.days {
+                Some(days) => days.collect::<Vec<_>>(),
+                None => AoC2024::DAYS
+                    .filter(|day| AoC2024::is_implemented(*day))
+                    .collect(),
+            };
+            days.into_iter()
+                .map(|day| {
+                    let input = inputs.find(day);
+                    let answers = inputs.answers(day);
+                    let loaded = load_day(&keys, args.plain, day, input.as_deref(), Some(&answers));
+                    (day, loaded)
+                })
+                .collect()
+        }
+    };
+    let results = pool::map(loaded, args.jobs.get(), |(day, loaded)| match loaded {
+        Loaded::Ready { input, expected } => (day, run_day(opts, day, input, &expected)),
+        Loaded::Done(status) => (day, *status),
+    });
+
+    if args.format == Format::Table {
+        print_summary(&results);
+    } else {
+        print!("{}", report::render(args.format, &results));
+    }
+    for (day, status) in results.iter() {
+        match status {
+            Status::InvalidInput(e) => eprintln!("error: {e}"),
+            status => {
+                for (part, failure) in status.failures() {
+                    eprintln!("error: day {day} part {part} {failure}");
+                }
+            }
+        }
+    }
+    if args.time && args.format == Format::Table {
+        println!();
+        print_timings(&results);
+    }
+    if args.alloc_stats && args.format == Format::Table {
+        println!();
+        print_allocations(&results);
+    }
+
+    let failed = results
+        .iter()
+        .filter(|(_, status)| {
+            matches!(status, Status::Ran { verdict: (p1, p2), .. } if p1.combine(*p2) == Verdict::Fail)
+        })
+        .count();
+    if failed > 0 {
+        return Err(eyre!("{failed} day(s) produced wrong answers"));
+    }
+    let aborted = results
+        .iter()
+        .filter(|(_, status)| {
+            status
+                .failures()
+                .iter()
+                .any(|(_, failure)| matches!(failure, Failure::Crashed(_) | Failure::TimedOut(_)))
+        })
+        .count();
+    if aborted > 0 {
+        return Err(eyre!("{aborted} day(s) crashed or timed out"));
+    }
+    let invalid = results
+        .iter()
+        .filter(|(_, status)| matches!(status, Status::InvalidInput(_)))
+        .count();
+    if invalid > 0 {
+        return Err(eyre!("{invalid} day(s) have invalid input"));
+    }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use crate::{parse_timeout, AoCRunner};
+
+    #[test]
+    fn test_cli() {
+        AoCRunner::command().debug_assert();
+    }
+
+    #[test]
+    fn test_parse_timeout() {
+        assert_eq!(parse_timeout("2.5"), Ok(2.5));
+        assert_eq!(parse_timeout("0"), Ok(0.0));
+        for invalid in ["inf", "NaN", "-1", "1e30", "soon"] {
+            assert!(parse_timeout(invalid).is_err(), "{invalid}");
+        }
+    }
+}
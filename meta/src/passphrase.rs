@@ -0,0 +1,50 @@
+use std::path::Path;
+
+use color_eyre::{
+    eyre::{bail, eyre},
+    Result,
+};
+use secrecy::{ExposeSecret, SecretString};
+
+fn from_contents(mut contents: String) -> Result<SecretString> {
+    let len = contents.trim_end_matches(['\r', '\n']).len();
+    contents.truncate(len);
+    if contents.is_empty() {
+        bail!("passphrase is empty");
+    }
+    Ok(SecretString::from(contents))
+}
+
+/// Reads the passphrase from the first line of a file, e.g. one with `0600` permissions or a named pipe.
+pub fn read_file(path: &Path) -> Result<SecretString> {
+    let contents = std::fs::read_to_string(path).map_err(|e| eyre!("{}: {e}", path.display()))?;
+    from_contents(contents.lines().next().unwrap_or_default().to_owned())
+}
+
+/// Reads the passphrase from an inherited file descriptor, e.g. `--passphrase-fd 3 3< <(pass aoc)`.
+#[cfg(unix)]
+pub fn read_fd(fd: i32) -> Result<SecretString> {
+    if fd <= 2 {
+        bail!("refusing to read the passphrase from stdio descriptor {fd}");
+    }
+    // Opening it by path leaves the descriptor itself alone, and a closed one is a plain error.
+    read_file(&Path::new("/dev/fd").join(fd.to_string()))
+}
+
+/// Asks for the passphrase on the terminal without echoing it.
+pub fn prompt(prompt: &str) -> Result<SecretString> {
+    let passphrase = rpassword::prompt_password(prompt).map_err(|e| {
+        eyre!("cannot prompt for the passphrase ({e}), use AGE_PASSPHRASE, --passphrase-file or --passphrase-fd")
+    })?;
+    from_contents(passphrase)
+}
+
+/// Like [`prompt`], but asks twice so a typo does not lock an input away.
+pub fn prompt_new(prompt: &str) -> Result<SecretString> {
+    let passphrase = self::prompt(prompt)?;
+    let confirm = self::prompt("Confirm passphrase: ")?;
+    if passphrase.expose_secret() != confirm.expose_secret() {
+        bail!("passphrases do not match");
+    }
+    Ok(passphrase)
+}
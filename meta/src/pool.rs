@@ -0,0 +1,54 @@
+use std::{sync::Mutex, thread};
+
+/// Maps `items` with `f` on up to `jobs` threads and returns the results in the order of
+/// `items`. With a single job everything runs on the calling thread.
+pub fn map<T: Send, R: Send>(items: Vec<T>, jobs: usize, f: impl Fn(T) -> R + Sync) -> Vec<R> {
+    let jobs = jobs.min(items.len());
+    if jobs <= 1 {
+        return items.into_iter().map(f).collect();
+    }
+
+    let queue = Mutex::new(items.into_iter().enumerate());
+    let results = Mutex::new(Vec::new());
+    thread::scope(|s| {
+        for _ in 0..jobs {
+            s.spawn(|| loop {
+                let Some((i, item)) = queue.lock().unwrap().next() else {
+                    break;
+                };
+                let result = f(item);
+                results.lock().unwrap().push((i, result));
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap();
+    results.sort_unstable_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    #[test]
+    fn test_order() {
+        let items = (0..20u64).collect::<Vec<_>>();
+        let results = super::map(items, 4, |i| {
+            // the early items finish last
+            thread::sleep(Duration::from_millis(20 - i));
+            (i, thread::current().id())
+        });
+        assert_eq!(
+            results.iter().map(|(i, _)| *i).collect::<Vec<_>>(),
+            (0..20).collect::<Vec<_>>()
+        );
+        assert!(results.iter().any(|(_, id)| *id != results[0].1));
+
+        let results = super::map(vec![1, 2], 1, |i| (i, thread::current().id()));
+        assert_eq!(
+            results,
+            [(1, thread::current().id()), (2, thread::current().id())]
+        );
+    }
+}
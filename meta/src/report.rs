@@ -0,0 +1,342 @@
+//! Machine-readable renderings of the per-day results, for dashboards and README tables.
+
+use std::time::Duration;
+
+use clap::ValueEnum;
+use meta::{
+    alloc::{AllocReport, AllocStats},
+    isolate::Failure,
+    timing::{PhaseStats, Timings},
+};
+use serde::Serialize;
+
+use crate::{check_cell, Status};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Aligned plain-text tables
+    #[default]
+    Table,
+    Json,
+    Csv,
+    Markdown,
+}
+
+#[derive(Debug, Serialize)]
+struct Record<'a> {
+    day: usize,
+    part1: Option<&'a str>,
+    part2: Option<&'a str>,
+    status: &'static str,
+    /// Why the day or a part of it failed to run, for every status but `ok`.
+    error: Option<String>,
+    check: Option<String>,
+    timings: Option<TimingsNanos>,
+    allocations: Option<AllocsReport>,
+}
+
+#[derive(Debug, Serialize)]
+struct AllocsReport {
+    parse: Allocs,
+    part1: Allocs,
+    part2: Allocs,
+}
+
+#[derive(Debug, Serialize)]
+struct Allocs {
+    count: u64,
+    bytes: u64,
+    peak_bytes: u64,
+}
+
+impl From<AllocStats> for Allocs {
+    fn from(stats: AllocStats) -> Self {
+        Allocs {
+            count: stats.allocations,
+            bytes: stats.bytes,
+            peak_bytes: stats.peak,
+        }
+    }
+}
+
+impl From<&AllocReport> for AllocsReport {
+    fn from(report: &AllocReport) -> Self {
+        AllocsReport {
+            parse: report.parse.into(),
+            part1: report.part1.into(),
+            part2: report.part2.into(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct TimingsNanos {
+    parse: StatsNanos,
+    part1: StatsNanos,
+    part2: StatsNanos,
+}
+
+#[derive(Debug, Serialize)]
+struct StatsNanos {
+    min_ns: u64,
+    median_ns: u64,
+    max_ns: u64,
+}
+
+fn nanos(d: Duration) -> u64 {
+    d.as_nanos().try_into().unwrap_or(u64::MAX)
+}
+
+impl From<&PhaseStats> for StatsNanos {
+    fn from(stats: &PhaseStats) -> Self {
+        StatsNanos {
+            min_ns: nanos(stats.min),
+            median_ns: nanos(stats.median),
+            max_ns: nanos(stats.max),
+        }
+    }
+}
+
+impl From<&Timings> for TimingsNanos {
+    fn from(timings: &Timings) -> Self {
+        TimingsNanos {
+            parse: (&timings.parse).into(),
+            part1: (&timings.part1).into(),
+            part2: (&timings.part2).into(),
+        }
+    }
+}
+
+impl<'a> Record<'a> {
+    fn new(day: usize, status: &'a Status) -> Self {
+        let mut record = Record {
+            day,
+            part1: None,
+            part2: None,
+            status: "",
+            error: None,
+            check: None,
+            timings: None,
+            allocations: None,
+        };
+        record.status = match status {
+            Status::Ran {
+                answers: (part1, part2),
+                timings,
+                allocations,
+                ..
+            } => {
+                record.part1 = part1.as_deref().ok();
+                record.part2 = part2.as_deref().ok();
+                record.check = Some(check_cell(status));
+                record.timings = timings.as_deref().map(TimingsNanos::from);
+                record.allocations = allocations.as_ref().map(AllocsReport::from);
+                let failures = status.failures();
+                if !failures.is_empty() {
+                    let errors = failures
+                        .iter()
+                        .map(|(part, failure)| format!("part {part} {failure}"));
+                    record.error = Some(errors.collect::<Vec<_>>().join("; "));
+                }
+                match failures.first() {
+                    None => "ok",
+                    Some((_, Failure::Unimplemented(_))) => "unimplemented",
+                    Some((_, Failure::Crashed(_))) => "crashed",
+                    Some((_, Failure::TimedOut(_))) => "timed-out",
+                }
+            }
+            Status::Unimplemented => "unimplemented",
+            Status::MissingInput => "missing-input",
+            Status::InvalidInput(e) => {
+                record.error = Some(e.to_string());
+                "invalid-input"
+            }
+            Status::Failed(e) => {
+                record.error = Some(e.clone());
+                "failed"
+            }
+        };
+        record
+    }
+}
+
+/// Renders `results` in one of the machine-readable formats, [`Format::Table`] is printed by the
+/// caller.
+pub fn render(format: Format, results: &[(usize, Status)]) -> String {
+    let records = results
+        .iter()
+        .map(|(day, status)| Record::new(*day, status))
+        .collect::<Vec<_>>();
+    match format {
+        Format::Table => unreachable!("tables are printed directly"),
+        Format::Json => {
+            let mut json = serde_json::to_string_pretty(&records).expect("records serialize");
+            json.push('\n');
+            json
+        }
+        Format::Csv => csv(&records),
+        Format::Markdown => markdown(&records),
+    }
+}
+
+fn median(stats: &StatsNanos) -> String {
+    stats.median_ns.to_string()
+}
+
+fn csv(records: &[Record]) -> String {
+    let field = |s: &str| {
+        if s.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", s.replace('"', "\"\""))
+        } else {
+            s.to_owned()
+        }
+    };
+    let mut out = "day,part1,part2,status,check,parse_ns,part1_ns,part2_ns,error\n".to_owned();
+    for record in records {
+        let timings = record.timings.as_ref();
+        let row = [
+            record.day.to_string(),
+            record.part1.unwrap_or_default().to_owned(),
+            record.part2.unwrap_or_default().to_owned(),
+            record.status.to_owned(),
+            record.check.clone().unwrap_or_default(),
+            timings.map(|t| median(&t.parse)).unwrap_or_default(),
+            timings.map(|t| median(&t.part1)).unwrap_or_default(),
+            timings.map(|t| median(&t.part2)).unwrap_or_default(),
+            record.error.clone().unwrap_or_default(),
+        ];
+        out.push_str(&row.map(|cell| field(&cell)).join(","));
+        out.push('\n');
+    }
+    out
+}
+
+fn markdown(records: &[Record]) -> String {
+    let cell = |s: &str| s.replace('|', "\\|").replace('\n', " ");
+    let timed = records.iter().any(|record| record.timings.is_some());
+    let mut header = vec!["Day", "Part 1", "Part 2", "Status", "Check"];
+    if timed {
+        header.extend(["Parse", "Part 1 time", "Part 2 time"]);
+    }
+    header.push("Error");
+    let mut out = format!("| {} |\n", header.join(" | "));
+    out.push_str(&format!(
+        "|{}\n",
+        header.iter().map(|_| " --- |").collect::<String>()
+    ));
+    for record in records {
+        let mut row = vec![
+            record.day.to_string(),
+            cell(record.part1.unwrap_or("-")),
+            cell(record.part2.unwrap_or("-")),
+            record.status.to_owned(),
+            record.check.clone().unwrap_or_else(|| "-".to_owned()),
+        ];
+        if timed {
+            let duration =
+                |stats: &StatsNanos| format!("{:?}", Duration::from_nanos(stats.median_ns));
+            match &record.timings {
+                Some(t) => row.extend([duration(&t.parse), duration(&t.part1), duration(&t.part2)]),
+                None => row.extend(["-".to_owned(), "-".to_owned(), "-".to_owned()]),
+            }
+        }
+        row.push(record.error.as_deref().map_or_else(|| "-".to_owned(), cell));
+        out.push_str(&format!("| {} |\n", row.join(" | ")));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use meta::{
+        isolate::Failure,
+        timing::{PhaseStats, Timings},
+        verify::Verdict,
+    };
+
+    use crate::{
+        report::{render, Format},
+        Status,
+    };
+
+    fn results() -> Vec<(usize, Status)> {
+        let stats = |micros| PhaseStats::from_samples(vec![Duration::from_micros(micros)]);
+        vec![
+            (
+                1,
+                Status::Ran {
+                    answers: (Ok("11".to_owned()), Ok("a,b|c".to_owned())),
+                    verdict: (Verdict::Pass, Verdict::Unknown),
+                    timings: Some(Box::new(Timings {
+                        parse: stats(3),
+                        part1: stats(1),
+                        part2: stats(2),
+                    })),
+                    allocations: None,
+                },
+            ),
+            (2, Status::MissingInput),
+            (3, Status::Failed("bad \"key\"".to_owned())),
+            (
+                4,
+                Status::Ran {
+                    answers: (
+                        Ok("7".to_owned()),
+                        Err(Failure::TimedOut(Duration::from_secs(60))),
+                    ),
+                    verdict: (Verdict::Unknown, Verdict::Unknown),
+                    timings: None,
+                    allocations: None,
+                },
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_json() {
+        let json = render(Format::Json, &results());
+        let records: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(records[0]["part2"], "a,b|c");
+        assert_eq!(records[0]["check"], "pass");
+        assert_eq!(records[0]["timings"]["parse"]["median_ns"], 3000);
+        assert_eq!(records[1]["status"], "missing-input");
+        assert_eq!(records[1]["part1"], serde_json::Value::Null);
+        assert_eq!(records[2]["error"], "bad \"key\"");
+    }
+
+    #[test]
+    fn test_csv() {
+        assert_eq!(
+            render(Format::Csv, &results()),
+            "day,part1,part2,status,check,parse_ns,part1_ns,part2_ns,error\n\
+            1,11,\"a,b|c\",ok,pass,3000,1000,2000,\n\
+            2,,,missing-input,,,,,\n\
+            3,,,failed,,,,,\"bad \"\"key\"\"\"\n\
+            4,7,,timed-out,unknown,,,,part 2 timed out after 60s\n"
+        );
+    }
+
+    #[test]
+    fn test_markdown() {
+        let markdown = render(Format::Markdown, &results());
+        let lines = markdown.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines[..3],
+            [
+                "| Day | Part 1 | Part 2 | Status | Check | Parse | Part 1 time | Part 2 time | Error |",
+                "| --- | --- | --- | --- | --- | --- | --- | --- | --- |",
+                "| 1 | 11 | a,b\\|c | ok | pass | 3µs | 1µs | 2µs | - |",
+            ]
+        );
+        assert_eq!(
+            lines[4],
+            "| 3 | - | - | failed | - | - | - | - | bad \"key\" |"
+        );
+        assert_eq!(
+            lines[5],
+            "| 4 | 7 | - | timed-out | unknown | - | - | - | part 2 timed out after 60s |"
+        );
+    }
+}
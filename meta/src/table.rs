@@ -0,0 +1,23 @@
+/// Prints `rows` as a plain-text table with left-aligned, padded columns.
+pub fn print_table<const N: usize>(header: [&str; N], rows: &[[String; N]]) {
+    let mut widths = header.map(str::len);
+    for row in rows.iter() {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let print_row = |row: [&str; N]| {
+        let cells = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>();
+        println!("{}", cells.join(" | ").trim_end());
+    };
+    print_row(header);
+    println!("{}", widths.map(|w| "-".repeat(w)).join("-|-"));
+    for row in rows.iter() {
+        print_row(row.each_ref().map(String::as_str));
+    }
+}
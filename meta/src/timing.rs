@@ -0,0 +1,116 @@
+use std::{
+    fmt::Display,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use parsing::{Example, ParseError, TryParse};
+
+use crate::{Answers, DayVisitor};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhaseStats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl PhaseStats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "at least one sample");
+        samples.sort();
+        let mid = samples.len() / 2;
+        let median = if samples.len() % 2 == 0 {
+            (samples[mid - 1] + samples[mid]) / 2
+        } else {
+            samples[mid]
+        };
+        PhaseStats {
+            min: samples[0],
+            median,
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+impl Display for PhaseStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.min == self.max {
+            write!(f, "{:?}", self.median)
+        } else {
+            write!(f, "{:?} / {:?} / {:?}", self.min, self.median, self.max)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: PhaseStats,
+    pub part1: PhaseStats,
+    pub part2: PhaseStats,
+}
+
+/// Solves a day `iterations` times, timing parse, part 1 and part 2 separately.
+pub(crate) struct TimePhases<'a> {
+    pub input: &'a str,
+    pub iterations: usize,
+}
+
+impl DayVisitor for TimePhases<'_> {
+    type Output = Result<(Answers, Timings), ParseError>;
+
+    fn visit<D: TryParse + Example + 'static>(self, day: usize) -> Self::Output {
+        let iterations = self.iterations.max(1);
+        let mut parse = Vec::with_capacity(iterations);
+        let mut part1 = Vec::with_capacity(iterations);
+        let mut part2 = Vec::with_capacity(iterations);
+        let mut answers = None;
+
+        for _ in 0..iterations {
+            let start = Instant::now();
+            let parsed = D::try_parse_input(black_box(self.input)).map_err(|e| e.with_day(day))?;
+            parse.push(start.elapsed());
+
+            let start = Instant::now();
+            let p1 = black_box(D::solve_part1(&parsed));
+            part1.push(start.elapsed());
+
+            let start = Instant::now();
+            let p2 = black_box(D::solve_part2(&parsed));
+            part2.push(start.elapsed());
+
+            answers = Some(Answers {
+                part1: p1.to_string(),
+                part2: p2.to_string(),
+            });
+        }
+
+        Ok((
+            answers.expect("at least one iteration"),
+            Timings {
+                parse: PhaseStats::from_samples(parse),
+                part1: PhaseStats::from_samples(part1),
+                part2: PhaseStats::from_samples(part2),
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::timing::PhaseStats;
+
+    #[test]
+    fn test_stats() {
+        let ms = Duration::from_millis;
+        let stats = PhaseStats::from_samples(vec![ms(5), ms(1), ms(3)]);
+        assert_eq!(stats.min, ms(1));
+        assert_eq!(stats.median, ms(3));
+        assert_eq!(stats.max, ms(5));
+
+        let stats = PhaseStats::from_samples(vec![ms(4), ms(2)]);
+        assert_eq!(stats.median, ms(3));
+    }
+}
@@ -0,0 +1,138 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::Answers;
+
+/// Known-correct answers of a day, stored as `part1: <answer>` / `part2: <answer>` lines.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Expected {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl FromStr for Expected {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut expected = Expected::default();
+        for line in s.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            let (part, answer) = line
+                .split_once(':')
+                .ok_or_else(|| format!("expected `part<N>: <answer>`, got `{line}`"))?;
+            let answer = Some(answer.trim().to_owned()).filter(|a| !a.is_empty());
+            match part.trim() {
+                "part1" => expected.part1 = answer,
+                "part2" => expected.part2 = answer,
+                part => return Err(format!("unknown part `{part}`")),
+            }
+        }
+        Ok(expected)
+    }
+}
+
+impl Display for Expected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(part1) = &self.part1 {
+            writeln!(f, "part1: {part1}")?;
+        }
+        if let Some(part2) = &self.part2 {
+            writeln!(f, "part2: {part2}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail,
+    Unknown,
+}
+
+impl Verdict {
+    fn of(expected: Option<&String>, actual: &str) -> Self {
+        match expected {
+            Some(expected) if expected == actual => Verdict::Pass,
+            Some(_) => Verdict::Fail,
+            None => Verdict::Unknown,
+        }
+    }
+
+    /// Combines the verdicts of both parts: any failure fails the day, any pass without failures passes it.
+    pub fn combine(self, other: Self) -> Self {
+        match (self, other) {
+            (Verdict::Fail, _) | (_, Verdict::Fail) => Verdict::Fail,
+            (Verdict::Pass, _) | (_, Verdict::Pass) => Verdict::Pass,
+            _ => Verdict::Unknown,
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail => write!(f, "fail"),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl Expected {
+    pub fn part_mut(&mut self, part: u8) -> &mut Option<String> {
+        match part {
+            1 => &mut self.part1,
+            2 => &mut self.part2,
+            part => panic!("invalid part {part}"),
+        }
+    }
+
+    pub fn check(&self, answers: &Answers) -> (Verdict, Verdict) {
+        (
+            self.check_part(1, &answers.part1),
+            self.check_part(2, &answers.part2),
+        )
+    }
+
+    /// The verdict on the answer of only `part` (1 or 2).
+    pub fn check_part(&self, part: u8, answer: &str) -> Verdict {
+        match part {
+            1 => Verdict::of(self.part1.as_ref(), answer),
+            2 => Verdict::of(self.part2.as_ref(), answer),
+            part => panic!("invalid part {part}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        verify::{Expected, Verdict},
+        Answers,
+    };
+
+    #[test]
+    fn test_parse() {
+        let expected = "part1: 11\npart2: \n".parse::<Expected>().unwrap();
+        assert_eq!(expected.part1.as_deref(), Some("11"));
+        assert_eq!(expected.part2, None);
+        assert!("11\n31\n".parse::<Expected>().is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let expected = Expected {
+            part1: Some("11".to_owned()),
+            part2: Some("31".to_owned()),
+        };
+        let answers = Answers {
+            part1: "11".to_owned(),
+            part2: "30".to_owned(),
+        };
+        let (part1, part2) = expected.check(&answers);
+        assert_eq!(part1, Verdict::Pass);
+        assert_eq!(part2, Verdict::Fail);
+        assert_eq!(part1.combine(part2), Verdict::Fail);
+        assert_eq!(expected.check_part(2, "31"), Verdict::Pass);
+        assert_eq!(Verdict::Unknown.combine(Verdict::Pass), Verdict::Pass);
+    }
+}
@@ -0,0 +1,256 @@
+use std::{fmt::Display, str::FromStr};
+
+use aoc_traits::AdventOfCodeDay;
+
+/// Where and why a puzzle input could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Filled in by the runner, the day crates do not know their own number.
+    pub day: Option<usize>,
+    /// 1-based line of `text`.
+    pub line: usize,
+    /// 1-based column of `text`, counted in characters.
+    pub column: usize,
+    /// The offending text, cut off at the end of its line.
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn with_day(self, day: usize) -> Self {
+        ParseError {
+            day: Some(day),
+            ..self
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {day}, ")?;
+        }
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        if !self.text.is_empty() {
+            write!(f, ": `{}`", self.text)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Fallible counterpart of [`AdventOfCodeDay::parse_input`].
+pub trait TryParse: AdventOfCodeDay {
+    fn try_parse_input(input: &str) -> Result<Self::ParsedInput<'_>, ParseError>;
+}
+
+/// Unimplemented days accept anything.
+impl TryParse for () {
+    #[allow(clippy::unit_arg)]
+    fn try_parse_input(input: &str) -> Result<Self::ParsedInput<'_>, ParseError> {
+        Ok(<() as AdventOfCodeDay>::parse_input(input))
+    }
+}
+
+/// The example input from the puzzle text that a day's tests are written against.
+pub trait Example {
+    const EXAMPLE: &'static str;
+}
+
+impl Example for () {
+    const EXAMPLE: &'static str = "";
+}
+
+/// Lines of `s` without trailing whitespace and without the blank lines at its end, so CRLF
+/// line endings, a missing or an extra final newline and trailing spaces all parse alike.
+pub fn lines(s: &str) -> impl Iterator<Item = &str> {
+    s.trim_end().lines().map(str::trim_end)
+}
+
+/// Rewrites `input` into the canonical form every parser expects: `\n` line endings, no
+/// trailing whitespace and exactly one final newline.
+pub fn normalize(input: &str) -> String {
+    let mut normalized = lines(input).collect::<Vec<_>>().join("\n");
+    normalized.push('\n');
+    normalized
+}
+
+/// `input` with CRLF line endings, extra final newlines and trailing spaces, for testing that a
+/// parser copes with all of them.
+pub fn variants(input: &str) -> [String; 4] {
+    let lf = normalize(input);
+    [
+        lf.replace('\n', "\r\n"),
+        lf.trim_end().to_owned(),
+        format!("{lf}\n\n"),
+        lf.replace('\n', "  \n"),
+    ]
+}
+
+/// The whole puzzle input, used to locate the slices of it that fail to parse.
+#[derive(Debug, Clone, Copy)]
+pub struct Source<'a>(&'a str);
+
+impl<'a> Source<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Source(input)
+    }
+
+    /// Reports `span`, which should be a slice of the input; anything else is reported at the
+    /// end of the input.
+    pub fn error(&self, span: &str, message: impl Display) -> ParseError {
+        let offset = (span.as_ptr() as usize)
+            .checked_sub(self.0.as_ptr() as usize)
+            .filter(|offset| *offset <= self.0.len() && self.0.is_char_boundary(*offset))
+            .unwrap_or(self.0.len());
+        let before = &self.0[..offset];
+        ParseError {
+            day: None,
+            line: before.matches('\n').count() + 1,
+            column: before
+                .rsplit('\n')
+                .next()
+                .unwrap_or_default()
+                .chars()
+                .count()
+                + 1,
+            text: span.lines().next().unwrap_or_default().to_owned(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Reports the single character at byte `index` of `line`, e.g. an unknown cell of a grid.
+    pub fn error_at(&self, line: &str, index: usize, message: impl Display) -> ParseError {
+        let len = line[index..].chars().next().map_or(0, char::len_utf8);
+        self.error(&line[index..index + len], message)
+    }
+
+    pub fn number<T>(&self, s: &str) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        s.parse()
+            .map_err(|e| self.error(s, format_args!("invalid number ({e})")))
+    }
+
+    pub fn split_once(
+        &self,
+        s: &'a str,
+        delimiter: &str,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        s.split_once(delimiter)
+            .ok_or_else(|| self.error(s, format_args!("expected `{}`", delimiter.escape_debug())))
+    }
+
+    /// The lines of the input, see [`lines`].
+    pub fn lines(&self) -> impl Iterator<Item = &'a str> {
+        lines(self.0)
+    }
+
+    /// Blank-line separated sections, each without trailing whitespace. Lines that only hold
+    /// whitespace, e.g. the `\r` of a CRLF input, count as blank.
+    pub fn sections(&self) -> impl Iterator<Item = &'a str> {
+        let input = self.0;
+        let mut offset = 0;
+        let mut start = None;
+        let mut sections = vec![];
+        for line in input.split_inclusive('\n') {
+            if line.trim().is_empty() {
+                if let Some(start) = start.take() {
+                    sections.push(input[start..offset].trim_end());
+                }
+            } else if start.is_none() {
+                start = Some(offset);
+            }
+            offset += line.len();
+        }
+        if let Some(start) = start {
+            sections.push(input[start..].trim_end());
+        }
+        sections.into_iter()
+    }
+
+    /// The two sections of inputs like `rules\n\nupdates`.
+    pub fn two_sections(&self) -> Result<(&'a str, &'a str), ParseError> {
+        let mut sections = self.sections();
+        match (sections.next(), sections.next(), sections.next()) {
+            (Some(first), Some(second), None) => Ok((first, second)),
+            (_, _, Some(third)) => Err(self.error(third, "expected only two sections")),
+            _ => Err(self.error("", "expected two sections separated by a blank line")),
+        }
+    }
+
+    pub fn strip_prefix(&self, s: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+        s.strip_prefix(prefix)
+            .ok_or_else(|| self.error(s, format_args!("expected `{prefix}`")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ParseError, Source};
+
+    #[test]
+    fn test_location() {
+        let input = "3   4\n4   x3\näb c\n";
+        let src = Source::new(input);
+        let line = input.lines().nth(1).unwrap();
+        let e = src.number::<u32>(&line[4..]).unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 5, "x3"));
+        assert_eq!(
+            e.with_day(1).to_string(),
+            "day 1, line 2, column 5: invalid number (invalid digit found in string): `x3`"
+        );
+
+        let line = input.lines().nth(2).unwrap();
+        let e = src.error_at(line, 2, "unexpected character");
+        assert_eq!((e.line, e.column, e.text.as_str()), (3, 2, "b"));
+
+        let e = src.split_once(line, "   ").unwrap_err();
+        assert_eq!((e.line, e.column), (3, 1));
+        assert_eq!(e.message, "expected `   `");
+    }
+
+    #[test]
+    fn test_sections() {
+        let input = "47|53\r\n97|13 \r\n\r\n75,47\r\n\r\n\r\n";
+        let src = Source::new(input);
+        assert_eq!(src.two_sections().unwrap(), ("47|53\r\n97|13", "75,47"));
+        assert_eq!(
+            crate::lines(src.sections().next().unwrap()).collect::<Vec<_>>(),
+            ["47|53", "97|13"]
+        );
+
+        let e = Source::new("1\n\n2\n  \n3\n").two_sections().unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (5, 1, "3"));
+        assert!(Source::new("1\n2\n").two_sections().is_err());
+    }
+
+    #[test]
+    fn test_normalize() {
+        for input in crate::variants("1 2\n\n3\n") {
+            assert_eq!(crate::normalize(&input), "1 2\n\n3\n");
+        }
+    }
+
+    #[test]
+    fn test_outside_input() {
+        let src = Source::new("1\n2\n");
+        assert_eq!(
+            src.error("", "expected a blank line"),
+            ParseError {
+                day: None,
+                line: 3,
+                column: 1,
+                text: String::new(),
+                message: "expected a blank line".to_owned(),
+            }
+        );
+    }
+}
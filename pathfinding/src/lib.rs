@@ -0,0 +1,290 @@
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+/// Edges with a cost, out of any node. Closures from a node to its `(neighbor, cost)` pairs are
+/// graphs.
+pub trait Graph<N> {
+    fn edges(&self, node: N) -> impl IntoIterator<Item = (N, usize)>;
+}
+
+impl<N, F, I> Graph<N> for F
+where
+    F: Fn(N) -> I,
+    I: IntoIterator<Item = (N, usize)>,
+{
+    fn edges(&self, node: N) -> impl IntoIterator<Item = (N, usize)> {
+        self(node)
+    }
+}
+
+/// A cheapest path from the start, both included, and its cost.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N> {
+    pub cost: usize,
+    pub nodes: Vec<N>,
+}
+
+/// An entry of the open set, the lowest `priority` is popped first.
+#[derive(Debug, Clone, Copy)]
+struct State<N> {
+    priority: usize,
+    cost: usize,
+    node: N,
+}
+
+impl<N> Ord for State<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<N> PartialOrd for State<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N> PartialEq for State<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N> Eq for State<N> {}
+
+/// Follows `previous` back from `end` to the node without one.
+fn path<N: Copy + Eq + Hash>(previous: &HashMap<N, N>, cost: usize, end: N) -> Path<N> {
+    let mut nodes = vec![end];
+    while let Some(node) = previous.get(nodes.last().unwrap()) {
+        nodes.push(*node);
+    }
+    nodes.reverse();
+    Path { cost, nodes }
+}
+
+/// A cheapest path from `start` to a node that `is_goal`, visiting the nodes in order of their
+/// cost plus `heuristic`. The heuristic must never overestimate the cost left to a goal.
+pub fn astar<N: Copy + Eq + Hash>(
+    graph: &impl Graph<N>,
+    start: N,
+    mut is_goal: impl FnMut(&N) -> bool,
+    mut heuristic: impl FnMut(&N) -> usize,
+) -> Option<Path<N>> {
+    let mut dist = HashMap::from([(start, 0)]);
+    let mut previous = HashMap::new();
+    let mut heap = BinaryHeap::from([State {
+        priority: heuristic(&start),
+        cost: 0,
+        node: start,
+    }]);
+
+    while let Some(State { cost, node, .. }) = heap.pop() {
+        if is_goal(&node) {
+            return Some(path(&previous, cost, node));
+        }
+        if cost > dist[&node] {
+            continue;
+        }
+
+        for (next, step) in graph.edges(node) {
+            let cost = cost.saturating_add(step);
+            if dist.get(&next).is_some_and(|d| *d <= cost) {
+                continue;
+            }
+            dist.insert(next, cost);
+            previous.insert(next, node);
+            heap.push(State {
+                priority: cost.saturating_add(heuristic(&next)),
+                cost,
+                node: next,
+            });
+        }
+    }
+    None
+}
+
+/// A cheapest path from `start` to a node that `is_goal`.
+pub fn dijkstra<N: Copy + Eq + Hash>(
+    graph: &impl Graph<N>,
+    start: N,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N>> {
+    astar(graph, start, is_goal, |_| 0)
+}
+
+/// A path with the fewest steps from `start` to a node that `is_goal`, for graphs whose edges
+/// all cost the same.
+pub fn bfs<N, I>(
+    neighbors: impl Fn(N) -> I,
+    start: N,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N>>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut previous = HashMap::new();
+    let mut seen = HashSet::from([start]);
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((node, steps)) = queue.pop_front() {
+        if is_goal(&node) {
+            return Some(path(&previous, steps, node));
+        }
+        for next in neighbors(node) {
+            if seen.insert(next) {
+                previous.insert(next, node);
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+    None
+}
+
+/// Every cheapest path from a start to each node it reaches, as the cost and the nodes right
+/// before it on any of them.
+#[derive(Debug, Clone)]
+pub struct ShortestPaths<N> {
+    dist: HashMap<N, usize>,
+    predecessors: HashMap<N, Vec<N>>,
+}
+
+impl<N: Copy + Eq + Hash> ShortestPaths<N> {
+    /// Dijkstra's algorithm run until every node reachable from `start` is settled.
+    pub fn new(graph: &impl Graph<N>, start: N) -> Self {
+        let mut dist = HashMap::from([(start, 0)]);
+        let mut predecessors: HashMap<N, Vec<N>> = HashMap::new();
+        let mut heap = BinaryHeap::from([State {
+            priority: 0,
+            cost: 0,
+            node: start,
+        }]);
+
+        while let Some(State { cost, node, .. }) = heap.pop() {
+            if cost > dist[&node] {
+                continue;
+            }
+
+            for (next, step) in graph.edges(node) {
+                let cost = cost.saturating_add(step);
+                match dist.entry(next) {
+                    Entry::Occupied(d) if *d.get() < cost => continue,
+                    Entry::Occupied(d) if *d.get() == cost => {
+                        predecessors.entry(next).or_default().push(node);
+                        continue;
+                    }
+                    Entry::Occupied(mut d) => {
+                        d.insert(cost);
+                    }
+                    Entry::Vacant(d) => {
+                        d.insert(cost);
+                    }
+                }
+                predecessors.insert(next, vec![node]);
+                heap.push(State {
+                    priority: cost,
+                    cost,
+                    node: next,
+                });
+            }
+        }
+        ShortestPaths { dist, predecessors }
+    }
+
+    /// The cost of a cheapest path to `node`, `None` if it cannot be reached.
+    pub fn distance(&self, node: &N) -> Option<usize> {
+        self.dist.get(node).copied()
+    }
+
+    /// The nodes right before `node` on its cheapest paths.
+    pub fn predecessors(&self, node: &N) -> &[N] {
+        self.predecessors.get(node).map_or(&[], Vec::as_slice)
+    }
+
+    /// Every node on a cheapest path to any of `ends`, the ends included.
+    pub fn nodes_on_paths(&self, ends: impl IntoIterator<Item = N>) -> HashSet<N> {
+        let mut stack = ends
+            .into_iter()
+            .filter(|end| self.dist.contains_key(end))
+            .collect::<Vec<_>>();
+        let mut nodes = stack.iter().copied().collect::<HashSet<_>>();
+        while let Some(node) = stack.pop() {
+            for pred in self.predecessors(&node) {
+                if nodes.insert(*pred) {
+                    stack.push(*pred);
+                }
+            }
+        }
+        nodes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{astar, bfs, dijkstra, ShortestPaths};
+
+    /// A diamond with two paths of cost 3 from 0 to 3, and a dearer one through 4.
+    fn edges(node: u32) -> Vec<(u32, usize)> {
+        match node {
+            0 => vec![(1, 1), (2, 2), (4, 1)],
+            1 => vec![(3, 2)],
+            2 => vec![(3, 1)],
+            4 => vec![(3, 5)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let path = dijkstra(&edges, 0, |n| *n == 3).unwrap();
+        assert_eq!(path.cost, 3);
+        assert_eq!(path.nodes.first(), Some(&0));
+        assert_eq!(path.nodes.last(), Some(&3));
+        assert_eq!(path.nodes.len(), 3);
+
+        assert_eq!(dijkstra(&edges, 3, |n| *n == 0), None);
+        assert_eq!(dijkstra(&edges, 2, |n| *n == 2).unwrap().nodes, [2]);
+    }
+
+    #[test]
+    fn test_astar() {
+        // a 10x10 open grid, the heuristic is the manhattan distance to the corner
+        let edges = |(x, y): (i32, i32)| {
+            [(1, 0), (-1, 0), (0, 1), (0, -1)]
+                .map(|(dx, dy)| ((x + dx, y + dy), 1))
+                .into_iter()
+                .filter(|((x, y), _)| (0..10).contains(x) && (0..10).contains(y))
+        };
+        let heuristic = |(x, y): &(i32, i32)| (9 - x + 9 - y) as usize;
+        let path = astar(&edges, (0, 0), |n| *n == (9, 9), heuristic).unwrap();
+        assert_eq!(path.cost, 18);
+        assert_eq!(path.nodes.len(), 19);
+    }
+
+    #[test]
+    fn test_bfs() {
+        let neighbors = |n: u32| [n + 1, n * 2].into_iter().filter(|n| *n <= 20);
+        let path = bfs(neighbors, 1, |n| *n == 20).unwrap();
+        assert_eq!(path.nodes, [1, 2, 4, 5, 10, 20]);
+        assert_eq!(path.cost, 5);
+        assert_eq!(bfs(neighbors, 21, |n| *n == 20), None);
+    }
+
+    #[test]
+    fn test_shortest_paths() {
+        let paths = ShortestPaths::new(&edges, 0);
+        assert_eq!(paths.distance(&3), Some(3));
+        assert_eq!(paths.distance(&4), Some(1));
+        let mut preds = paths.predecessors(&3).to_vec();
+        preds.sort();
+        assert_eq!(preds, [1, 2]);
+
+        let mut nodes = paths.nodes_on_paths([3]).into_iter().collect::<Vec<_>>();
+        nodes.sort();
+        assert_eq!(nodes, [0, 1, 2, 3]);
+        assert!(paths.nodes_on_paths([5]).is_empty());
+    }
+}
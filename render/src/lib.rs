@@ -0,0 +1,369 @@
+//! Pictures of grid puzzles, with what a solver found drawn over the map. They print to a
+//! terminal in color or are saved as PPM or PNG images, and step by step solvers are saved as
+//! frames of an animation.
+
+use std::{
+    fmt::{self, Display, Write as _},
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use aoc_traits::AdventOfCodeDay;
+use grid::{Grid, Position};
+
+/// Draws the parsed input of a day.
+pub trait Render: AdventOfCodeDay {
+    fn render(input: &Self::ParsedInput<'_>) -> Picture;
+}
+
+/// A day whose solver moves from state to state, drawn one frame per state.
+pub trait Simulation: AdventOfCodeDay {
+    type State;
+
+    /// The states in order, from the one the input describes to the last.
+    fn states<'a>(input: &'a Self::ParsedInput<'_>) -> impl Iterator<Item = Self::State> + 'a;
+
+    fn draw(input: &Self::ParsedInput<'_>, state: &Self::State) -> Picture;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+    pub const GRAY: Color = Color::rgb(96, 96, 96);
+    pub const RED: Color = Color::rgb(220, 50, 47);
+    pub const GREEN: Color = Color::rgb(80, 200, 80);
+    pub const BLUE: Color = Color::rgb(38, 139, 210);
+    pub const YELLOW: Color = Color::rgb(240, 200, 40);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Color { r, g, b }
+    }
+
+    /// The `i`th of a cycle of colors that are easy to tell apart, e.g. for regions.
+    pub fn palette(i: usize) -> Self {
+        const PALETTE: [Color; 8] = [
+            Color::rgb(230, 25, 75),
+            Color::rgb(60, 180, 75),
+            Color::rgb(255, 225, 25),
+            Color::rgb(0, 130, 200),
+            Color::rgb(245, 130, 48),
+            Color::rgb(145, 30, 180),
+            Color::rgb(70, 240, 240),
+            Color::rgb(240, 50, 230),
+        ];
+        PALETTE[i % PALETTE.len()]
+    }
+}
+
+/// How a cell is drawn: a character in a color on the terminal, a square of that color in an
+/// image. An overlay puts a background behind it, which images show instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tile {
+    pub glyph: char,
+    pub color: Color,
+    pub background: Option<Color>,
+}
+
+impl Tile {
+    pub const fn new(glyph: char, color: Color) -> Self {
+        Tile {
+            glyph,
+            color,
+            background: None,
+        }
+    }
+
+    /// The color of the tile's pixels in an image.
+    pub fn pixel(&self) -> Color {
+        self.background.unwrap_or(self.color)
+    }
+}
+
+/// A grid of tiles. `Display` prints only the glyphs, [`Picture::ansi`] adds the colors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Picture {
+    tiles: Grid<Tile>,
+}
+
+impl Picture {
+    pub fn new<T>(grid: &Grid<T>, tile: impl FnMut(&T) -> Tile) -> Self {
+        Picture {
+            tiles: grid.map(tile),
+        }
+    }
+
+    pub fn tiles(&self) -> &Grid<Tile> {
+        &self.tiles
+    }
+
+    /// Draws `glyph` in `color` at `pos`, in front of any overlay there.
+    pub fn draw(&mut self, pos: Position, glyph: char, color: Color) -> &mut Self {
+        let tile = &mut self.tiles[pos];
+        tile.glyph = glyph;
+        tile.color = color;
+        self
+    }
+
+    /// Puts a `color` background behind `positions`, e.g. a path, the visited cells or a region.
+    pub fn overlay(
+        &mut self,
+        positions: impl IntoIterator<Item = Position>,
+        color: Color,
+    ) -> &mut Self {
+        for pos in positions {
+            self.tiles[pos].background = Some(color);
+        }
+        self
+    }
+
+    /// The glyphs with 24-bit color escape codes, for terminals.
+    pub fn ansi(&self) -> String {
+        let mut out = String::new();
+        for row in 0..self.tiles.rows() {
+            for tile in self.tiles.row(row) {
+                let Color { r, g, b } = tile.color;
+                write!(out, "\x1b[38;2;{r};{g};{b}m").unwrap();
+                if let Some(Color { r, g, b }) = tile.background {
+                    write!(out, "\x1b[48;2;{r};{g};{b}m").unwrap();
+                }
+                out.push(tile.glyph);
+                out.push_str("\x1b[0m");
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Row by row RGB bytes, each tile `scale` pixels wide and high.
+    fn pixels(&self, scale: usize) -> Vec<u8> {
+        let mut pixels =
+            Vec::with_capacity(self.tiles.rows() * self.tiles.cols() * scale * scale * 3);
+        for row in 0..self.tiles.rows() {
+            for _ in 0..scale {
+                for tile in self.tiles.row(row) {
+                    let Color { r, g, b } = tile.pixel();
+                    for _ in 0..scale {
+                        pixels.extend([r, g, b]);
+                    }
+                }
+            }
+        }
+        pixels
+    }
+
+    fn size(&self, scale: usize) -> (usize, usize) {
+        (self.tiles.cols() * scale, self.tiles.rows() * scale)
+    }
+
+    /// A binary PPM image, which most image viewers open.
+    pub fn write_ppm(&self, mut w: impl Write, scale: usize) -> io::Result<()> {
+        let (width, height) = self.size(scale);
+        write!(w, "P6\n{width} {height}\n255\n")?;
+        w.write_all(&self.pixels(scale))
+    }
+
+    pub fn write_png(&self, w: impl Write, scale: usize) -> io::Result<()> {
+        let (width, height) = self.size(scale);
+        let mut encoder = png::Encoder::new(w, width as u32, height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer
+            .write_image_data(&self.pixels(scale))
+            .map_err(io::Error::other)
+    }
+
+    /// Saves a PNG or PPM image, depending on the extension of `path`.
+    pub fn save(&self, path: &Path, scale: usize) -> io::Result<()> {
+        let ext = path.extension().and_then(|ext| ext.to_str());
+        let write = match ext {
+            Some("png") => Self::write_png,
+            Some("ppm") => Self::write_ppm,
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{}: expected a .png or .ppm file", path.display()),
+                ))
+            }
+        };
+        let mut file = BufWriter::new(File::create(path)?);
+        write(self, &mut file, scale)?;
+        file.flush()
+    }
+}
+
+/// Frames of an animation, saved as numbered PNG images in a directory or as an animated GIF
+/// if the path ends in `.gif`.
+pub struct Frames {
+    path: PathBuf,
+    scale: usize,
+    count: usize,
+    gif: Option<gif::Encoder<BufWriter<File>>>,
+}
+
+impl Frames {
+    /// How long each frame of a GIF shows, in hundredths of a second.
+    const DELAY: u16 = 10;
+
+    pub fn create(path: &Path, scale: usize) -> io::Result<Self> {
+        if !Self::is_gif(path) {
+            fs::create_dir_all(path)?;
+        }
+        Ok(Frames {
+            path: path.to_owned(),
+            scale,
+            count: 0,
+            gif: None,
+        })
+    }
+
+    fn is_gif(path: &Path) -> bool {
+        path.extension().is_some_and(|ext| ext == "gif")
+    }
+
+    /// The number of frames so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn push(&mut self, picture: &Picture) -> io::Result<()> {
+        if Self::is_gif(&self.path) {
+            self.push_gif(picture)?;
+        } else {
+            let path = self.path.join(format!("{:05}.png", self.count));
+            picture.save(&path, self.scale)?;
+        }
+        self.count += 1;
+        Ok(())
+    }
+
+    fn push_gif(&mut self, picture: &Picture) -> io::Result<()> {
+        let (width, height) = picture.size(self.scale);
+        let too_large = |_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{width}x{height} is too large for a GIF, try a smaller --scale"),
+            )
+        };
+        let (width, height) = (
+            u16::try_from(width).map_err(too_large)?,
+            u16::try_from(height).map_err(too_large)?,
+        );
+        let encoder = match &mut self.gif {
+            Some(encoder) => encoder,
+            None => {
+                let file = BufWriter::new(File::create(&self.path)?);
+                let mut encoder =
+                    gif::Encoder::new(file, width, height, &[]).map_err(io::Error::other)?;
+                encoder
+                    .set_repeat(gif::Repeat::Infinite)
+                    .map_err(io::Error::other)?;
+                self.gif.insert(encoder)
+            }
+        };
+        let mut frame = gif::Frame::from_rgb_speed(width, height, &picture.pixels(self.scale), 10);
+        frame.delay = Self::DELAY;
+        encoder.write_frame(&frame).map_err(io::Error::other)
+    }
+
+    /// Ends the GIF. Dropping the frames does too, but loses any error.
+    pub fn finish(self) -> io::Result<()> {
+        if let Some(encoder) = self.gif {
+            encoder.into_inner()?.flush()?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Picture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in 0..self.tiles.rows() {
+            for tile in self.tiles.row(row) {
+                f.write_char(tile.glyph)?;
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use grid::Grid;
+
+    use crate::{Color, Frames, Picture, Tile};
+
+    fn picture() -> Picture {
+        let grid = Grid::from_fn(2, 3, |(row, col)| row == col);
+        let mut picture = Picture::new(&grid, |wall| match wall {
+            true => Tile::new('#', Color::WHITE),
+            false => Tile::new('.', Color::GRAY),
+        });
+        picture
+            .overlay([(0, 2), (1, 1)], Color::RED)
+            .draw((1, 1), '@', Color::BLUE);
+        picture
+    }
+
+    #[test]
+    fn test_text() {
+        let picture = picture();
+        assert_eq!(picture.to_string(), "#..\n.@.\n");
+        assert_eq!(picture.tiles()[(1, 1)].background, Some(Color::RED));
+        let ansi = picture.ansi();
+        assert!(ansi.starts_with("\x1b[38;2;255;255;255m#\x1b[0m"));
+        assert!(ansi.contains("\x1b[38;2;96;96;96m\x1b[48;2;220;50;47m.\x1b[0m\n"));
+    }
+
+    #[test]
+    fn test_ppm() {
+        let mut ppm = vec![];
+        picture().write_ppm(&mut ppm, 2).unwrap();
+        let header = b"P6\n6 4\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        let pixels = &ppm[header.len()..];
+        assert_eq!(pixels.len(), 6 * 4 * 3);
+        // the overlaid top right tile, in both of its pixel rows
+        assert_eq!(pixels[4 * 3..5 * 3], [220, 50, 47]);
+        assert_eq!(pixels[(6 + 5) * 3..(6 + 6) * 3], [220, 50, 47]);
+        assert_eq!(pixels[(3 * 6 + 1) * 3..(3 * 6 + 2) * 3], [96, 96, 96]);
+    }
+
+    #[test]
+    fn test_png() {
+        let mut png = vec![];
+        picture().write_png(&mut png, 3).unwrap();
+        assert_eq!(png[..8], *b"\x89PNG\r\n\x1a\n");
+    }
+
+    #[test]
+    fn test_frames() {
+        let dir = std::env::temp_dir().join(format!("render-frames-{}", std::process::id()));
+        let mut frames = Frames::create(&dir, 1).unwrap();
+        frames.push(&picture()).unwrap();
+        frames.push(&picture()).unwrap();
+        assert_eq!(frames.count(), 2);
+        frames.finish().unwrap();
+        assert!(dir.join("00001.png").exists());
+
+        let gif = dir.join("frames.gif");
+        let mut frames = Frames::create(&gif, 2).unwrap();
+        frames.push(&picture()).unwrap();
+        frames.push(&picture()).unwrap();
+        frames.finish().unwrap();
+        let gif = fs::read(gif).unwrap();
+        assert_eq!(gif[..6], *b"GIF89a");
+        assert_eq!(gif.last(), Some(&b';'));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}